@@ -1,17 +1,32 @@
--- note: the hook's load will only fire after the mission loaded.
-local function load()
-  log.write("[GRPC-Hook]", log.INFO, "mission loaded, setting up gRPC listener ...")
+-- Load the native module. This is also done before the mission (and thus the gRPC server) is
+-- loaded, so that lifecycle events can be emitted right away.
+local module, isHotReload
+local function loadModule()
+  if module then
+    return module, isHotReload
+  end
 
   -- Let DCS know where to find the DLLs
   if not string.find(package.cpath, GRPC.dllPath) then
     package.cpath = package.cpath .. [[;]] .. GRPC.dllPath .. [[?.dll;]]
   end
 
-  local ok, grpc = pcall(require, "dcs_grpc_hot_reload")
+  local ok, hotReload = pcall(require, "dcs_grpc_hot_reload")
   if ok then
-    log.write("[GRPC-Hook]", log.INFO, "loaded hot reload version")
+    module, isHotReload = hotReload, true
   else
-    grpc = require("dcs_grpc")
+    module, isHotReload = require("dcs_grpc"), false
+  end
+  return module, isHotReload
+end
+
+-- note: the hook's load will only fire after the mission loaded.
+local function load()
+  log.write("[GRPC-Hook]", log.INFO, "mission loaded, setting up gRPC listener ...")
+
+  local grpc, hotReload = loadModule()
+  if hotReload then
+    log.write("[GRPC-Hook]", log.INFO, "loaded hot reload version")
   end
 
   _G.grpc = grpc
@@ -20,13 +35,54 @@ local function load()
   log.write("[GRPC-Hook]", log.INFO, "gRPC listener set up.")
 end
 
+-- Emit a lifecycle event. Some of the callbacks are also called while the gRPC server isn't
+-- running (e.g. while a mission loads), in which case the event is kept by the server and
+-- published once it is running.
+local function lifecycleEvent(event)
+  local ok, err = pcall(function()
+    loadModule().hookEvent({
+      time = DCS.getModelTime(),
+      event = event,
+    })
+  end)
+  if not ok then
+    log.write("[GRPC-Hook]", log.ERROR, "Failed to emit " .. event.type .. " event: " .. tostring(err))
+  end
+end
+
 local handler = {}
 
+function handler.onMissionLoadBegin()
+  lifecycleEvent({ type = "missionLoadBegin" })
+end
+
 function handler.onMissionLoadEnd()
   local ok, err = pcall(load)
   if not ok then
     log.write("[GRPC-Hook]", log.ERROR, "Failed to set up gRPC listener: "..tostring(err))
+    return
   end
+
+  lifecycleEvent({ type = "missionLoadEnd" })
+end
+
+function handler.onSimulationStart()
+  lifecycleEvent({ type = "simulationStart" })
+end
+
+function handler.onSimulationPause()
+  lifecycleEvent({ type = "simulationPause" })
+end
+
+function handler.onSimulationResume()
+  lifecycleEvent({ type = "simulationResume" })
+end
+
+function handler.onNetMissionChanged(newMissionName)
+  lifecycleEvent({
+    type = "netMissionChanged",
+    missionName = newMissionName,
+  })
 end
 
 function handler.onSimulationFrame()
@@ -38,26 +94,61 @@ end
 function handler.onSimulationStop()
   log.write("[GRPC-Hook]", log.INFO, "simulation stopped, shutting down gRPC listener ...")
 
+  lifecycleEvent({ type = "simulationStop" })
   GRPC.stop()
   grpc = nil
 end
 
 -- None of these methods should return anything as doing so breaks other scripts attempting to
--- react to the hook as well.
+-- react to the hook as well. The only exception is a gatekeeper denying a player to connect or to
+-- change into a slot.
+
+-- Ask the connected gatekeepers (see `GatekeeperService`) for a verdict (players with an active
+-- ban are denied to connect right away). Returns `nil` unless the decision got denied.
+local function gatekeep(decision)
+  if not grpc then
+    return nil
+  end
+
+  local verdict = grpc.gatekeep(decision)
+  if verdict and not verdict.allow then
+    return verdict
+  end
+  return nil
+end
 
 function handler.onPlayerTrySendChat(playerID, msg)
   -- note: currently `all` (third parameter) will always `=true` regardless if the target is to the coalition/team
   --        or to everybody. When ED fixes this, implementation should determine the dcs.common.v0.Coalition
 
-  grpc.event({
-    time = DCS.getModelTime(),
-    event = {
-      type = "playerSendChat",
+  if grpc then
+    grpc.event({
+      time = DCS.getModelTime(),
+      event = {
+        type = "playerSendChat",
+        playerId = playerID,
+        message = msg
+      },
+    })
+
+    local playerInfo = net.get_player_info(playerID) or {}
+    local result = grpc.chatCommand({
       playerId = playerID,
-      message = msg
-    },
-  })
-
+      playerName = playerInfo.name or "",
+      ucid = playerInfo.ucid or "",
+      coalition = (playerInfo.side or 0) + 1, -- offsetting for grpc COALITION enum
+      message = msg,
+    })
+    if result then
+      if result.reply then
+        net.send_chat_to(result.reply, playerID)
+      end
+      if result.suppress then
+        -- returning an empty string hides the message from the chat
+        return ""
+      end
+    end
+  end
 end
 
 function handler.onPlayerTryConnect(addr, name, ucid, id)
@@ -71,6 +162,18 @@ function handler.onPlayerTryConnect(addr, name, ucid, id)
       id = id,
     },
   })
+
+  local denied = gatekeep({
+    tryConnect = {
+      addr = addr,
+      name = name,
+      ucid = ucid,
+      playerId = id,
+    },
+  })
+  if denied then
+    return false, denied.reason or "Connection refused"
+  end
 end
 
 function handler.onPlayerDisconnect(id, reason)
@@ -98,4 +201,44 @@ function handler.onPlayerChangeSlot(playerId)
   })
 end
 
+function handler.onPlayerTryChangeSlot(playerId, side, slotId)
+  lifecycleEvent({
+    type = "playerTryChangeSlot",
+    playerId = playerId,
+    coalition = side + 1, -- offsetting for grpc COALITION enum
+    slotId = tostring(slotId),
+  })
+
+  local playerInfo = net.get_player_info(playerId) or {}
+  local denied = gatekeep({
+    tryChangeSlot = {
+      playerId = playerId,
+      name = playerInfo.name or "",
+      ucid = playerInfo.ucid or "",
+      coalition = side + 1, -- offsetting for grpc COALITION enum
+      slotId = tostring(slotId),
+    },
+  })
+  if denied then
+    if denied.reason then
+      net.send_chat_to(denied.reason, playerId)
+    end
+    return false
+  end
+end
+
+function handler.onPlayerStart(playerId)
+  lifecycleEvent({
+    type = "playerStart",
+    playerId = playerId,
+  })
+end
+
+function handler.onPlayerStop(playerId)
+  lifecycleEvent({
+    type = "playerStop",
+    playerId = playerId,
+  })
+end
+
 DCS.setUserCallbacks(handler)
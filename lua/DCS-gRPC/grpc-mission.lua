@@ -3,7 +3,15 @@ if not GRPC then
     -- scaffold nested tables to allow direct assignment in config file
     tts = { provider = { gcloud = {}, aws = {}, azure = {}, win = {} } },
     srs = {},
-    auth = { tokens = {} }
+    auth = { tokens = {} },
+    grpcWeb = { corsOrigins = {} },
+    gateway = {},
+    websocket = {},
+    streams = {},
+    gatekeeper = {},
+    bans = {},
+    players = {},
+    zones = {}
   }
 end
 
@@ -21,7 +21,17 @@ if isMissionEnv then
     integrityCheckDisabled = GRPC.integrityCheckDisabled,
     tts = GRPC.tts,
     srs = GRPC.srs,
-    auth = GRPC.auth
+    auth = GRPC.auth,
+    grpcWeb = GRPC.grpcWeb,
+    gateway = GRPC.gateway,
+    websocket = GRPC.websocket,
+    webhooks = GRPC.webhooks,
+    eventSinks = GRPC.eventSinks,
+    streams = GRPC.streams,
+    gatekeeper = GRPC.gatekeeper,
+    bans = GRPC.bans,
+    players = GRPC.players,
+    zones = GRPC.zones
   }))
 end
 
@@ -33,6 +43,13 @@ end
 GRPC.exporters = {}
 dofile(GRPC.luaPath .. [[exporters\object.lua]])
 
+--
+-- Import methods
+--
+
+GRPC.importers = {}
+dofile(GRPC.luaPath .. [[importers\task.lua]])
+
 --
 -- Helper methods
 --
@@ -144,6 +161,28 @@ GRPC.errorPermissionDenied = function(msg)
 end
 
 GRPC.event = grpc.event
+
+--- Emit a custom event with the given `name` and optional `payload` (a table) to all
+--- `StreamEvents` subscribers.
+GRPC.customEvent = function(name, payload)
+  if type(name) ~= "string" or name == "" then
+    GRPC.logError("GRPC.customEvent: expected a non-empty string as name, got "..tostring(name))
+    return
+  end
+  if payload ~= nil and type(payload) ~= "table" then
+    GRPC.logError("GRPC.customEvent: expected a table as payload for `"..name.."`, got "..type(payload))
+    return
+  end
+
+  grpc.event({
+    time = isMissionEnv and timer.getTime() or DCS.getModelTime(),
+    event = {
+      type = "custom",
+      name = name,
+      payload = payload or {},
+    },
+  })
+end
 --
 -- RPC methods
 --
@@ -155,12 +194,14 @@ dofile(GRPC.luaPath .. [[methods\controllers.lua]])
 dofile(GRPC.luaPath .. [[methods\custom.lua]])
 dofile(GRPC.luaPath .. [[methods\group.lua]])
 dofile(GRPC.luaPath .. [[methods\hook.lua]])
+dofile(GRPC.luaPath .. [[methods\land.lua]])
 dofile(GRPC.luaPath .. [[methods\mission.lua]])
 dofile(GRPC.luaPath .. [[methods\net.lua]])
 dofile(GRPC.luaPath .. [[methods\timer.lua]])
 dofile(GRPC.luaPath .. [[methods\trigger.lua]])
 dofile(GRPC.luaPath .. [[methods\unit.lua]])
 dofile(GRPC.luaPath .. [[methods\world.lua]])
+dofile(GRPC.luaPath .. [[methods\zone.lua]])
 
 --
 -- RPC request handler
@@ -0,0 +1,178 @@
+--
+-- Converts GRPC task messages (see common/v0/task.proto) into DCS task tables
+-- https://wiki.hoggitworld.com/view/DCS_task
+--
+
+local function toVec2(position)
+  local point = coord.LLtoLO(position.lat, position.lon)
+  return {x = point.x, y = point.z}
+end
+
+local orbitPatterns = {
+  [0] = "Circle", -- PATTERN_UNSPECIFIED
+  [1] = "Circle",
+  [2] = "Race-Track",
+}
+
+local expends = {
+  [0] = "Auto", -- EXPEND_UNSPECIFIED
+  [1] = "One",
+  [2] = "Two",
+  [3] = "Four",
+  [4] = "Quarter",
+  [5] = "Half",
+  [6] = "All",
+}
+
+-- Parameters shared by the attack tasks
+local function attackParams(task)
+  return {
+    weaponType = task.weaponType,
+    expend = expends[task.expend] or "Auto",
+    altitudeEnabled = task.altitude ~= nil,
+    altitude = task.altitude,
+    directionEnabled = task.direction ~= nil,
+    direction = task.direction and math.rad(task.direction),
+    groupAttack = task.groupAttack,
+  }
+end
+
+local function groupId(name)
+  local group = Group.getByName(name)
+  if group == nil then
+    return nil, GRPC.errorNotFound("Could not find group with name '" .. name .. "'")
+  end
+  return group:getID()
+end
+
+-- Parameters shared by the follow and escort tasks
+local function followParams(task)
+  local id, err = groupId(task.groupName)
+  if id == nil then
+    return nil, err
+  end
+
+  local offset = task.offset or {}
+  return {
+    groupId = id,
+    pos = {x = offset.x or 0, y = offset.y or 0, z = offset.z or 0},
+    lastWptIndexFlag = task.lastWaypoint ~= nil,
+    lastWptIndex = task.lastWaypoint,
+  }
+end
+
+local importers = {
+  orbit = function(task)
+    local params = {
+      pattern = orbitPatterns[task.pattern] or "Circle",
+      speed = task.speed,
+      altitude = task.altitude,
+    }
+    if task.position ~= nil then
+      params.point = toVec2(task.position)
+    end
+    if task.position2 ~= nil then
+      params.point2 = toVec2(task.position2)
+    end
+
+    return {id = "Orbit", params = params}
+  end,
+
+  land = function(task)
+    return {
+      id = "Land",
+      params = {
+        point = toVec2(task.position),
+        durationFlag = task.duration ~= nil,
+        duration = task.duration,
+      },
+    }
+  end,
+
+  refueling = function()
+    return {id = "Refueling", params = {}}
+  end,
+
+  attackGroup = function(task)
+    local id, err = groupId(task.groupName)
+    if id == nil then
+      return nil, err
+    end
+
+    local params = attackParams(task)
+    params.groupId = id
+    return {id = "AttackGroup", params = params}
+  end,
+
+  attackUnit = function(task)
+    local unit = Unit.getByName(task.unitName)
+    if unit == nil then
+      return nil, GRPC.errorNotFound("Could not find unit with name '" .. task.unitName .. "'")
+    end
+
+    local params = attackParams(task)
+    params.unitId = unit:getID()
+    return {id = "AttackUnit", params = params}
+  end,
+
+  bombing = function(task)
+    local params = attackParams(task)
+    params.point = toVec2(task.position)
+    params.attackQtyLimit = task.attackQuantity ~= nil
+    params.attackQty = task.attackQuantity
+    return {id = "Bombing", params = params}
+  end,
+
+  follow = function(task)
+    local params, err = followParams(task)
+    if params == nil then
+      return nil, err
+    end
+
+    return {id = "Follow", params = params}
+  end,
+
+  escort = function(task)
+    local params, err = followParams(task)
+    if params == nil then
+      return nil, err
+    end
+
+    params.engagementDistMax = task.engagementDistance
+    params.targetTypes = task.targetTypes or {}
+    return {id = "Escort", params = params}
+  end,
+}
+
+-- Returns the DCS task, or nil and a GRPC error if the task is not supported
+-- or references a group or unit that doesn't exist.
+GRPC.importers.task = function(task)
+  for kind, params in pairs(task.task or {}) do
+    local importer = importers[kind]
+    if importer ~= nil then
+      return importer(params)
+    end
+  end
+
+  return nil, GRPC.errorInvalidArgument("Unsupported task")
+end
+
+-- Wrap a list of tasks into a `ComboTask` as used by the tasks of a route
+-- point. Raises the GRPC error (as table) if any of the tasks cannot be
+-- imported.
+GRPC.importers.comboTask = function(tasks)
+  local result = {}
+  for i, task in ipairs(tasks or {}) do
+    local imported, err = GRPC.importers.task(task)
+    if imported == nil then
+      error(err, 0)
+    end
+
+    imported.number = i
+    imported.enabled = true
+    imported.auto = false
+    table.insert(result, imported)
+  end
+
+  return {id = "ComboTask", params = {tasks = result}}
+end
@@ -21,12 +21,9 @@ local skill = {
   Player = 5
 }
 
---local altitudeType = {
---  [1] = "BARO",
---  [2] = "RADIO",
---  BARO = 1,
---  RADIO = 2
---}
+local function toVec3(position)
+  return coord.LLtoLO(position.lat, position.lon, position.alt)
+end
 
 local createGroundUnitsTemplate = function(unitListTemplate)
   local units = {}
@@ -43,12 +40,246 @@ local createGroundUnitsTemplate = function(unitListTemplate)
       heading = unitTemplate.heading,
       playerCanDrive = true
     }
+    if unitTemplate.unitId ~= nil then
+      unit.unitId = unitTemplate.unitId
+    end
     table.insert(units, unit)
   end
 
   return units
 end
 
+local createShipUnitsTemplate = function(unitListTemplate)
+  local units = {}
+
+  for _, unitTemplate in ipairs(unitListTemplate) do
+    local pos = coord.LLtoLO(unitTemplate.position.lat, unitTemplate.position.lon)
+    local unit = {
+      name = unitTemplate.name,
+      type = unitTemplate.type,
+      x = pos.x,
+      y = pos.z,
+      skill = skill[unitTemplate.skill],
+      heading = math.rad(unitTemplate.heading or 0),
+      transportable = { randomTransportable = false },
+    }
+    if unitTemplate.unitId ~= nil then
+      unit.unitId = unitTemplate.unitId
+    end
+    if unitTemplate.frequency ~= nil then
+      unit.frequency = unitTemplate.frequency
+      unit.modulation = unitTemplate.modulation
+    end
+    table.insert(units, unit)
+  end
+
+  return units
+end
+
+local pointTypes = {
+  [0] = "Turning Point", -- POINT_TYPE_RANDOM
+  [1] = "TakeOff",
+  [2] = "TakeOffParking",
+  [3] = "Turning Point",
+  [4] = "TakeOffParkingHot",
+  [5] = "Land",
+}
+
+-- The action of aircraft waypoints if none is provided
+local aircraftActions = {
+  [1] = "From Runway", -- POINT_TYPE_TAKEOFF
+  [2] = "From Parking Area", -- POINT_TYPE_TAKEOFF_PARKING
+  [4] = "From Parking Area Hot", -- POINT_TYPE_TAKEOFF_PARKING_HOT
+  [5] = "Landing", -- POINT_TYPE_LAND
+}
+
+local altitudeTypes = {
+  [0] = "BARO", -- ALTITUDE_TYPE_UNSPECIFIED
+  [1] = "BARO",
+  [2] = "RADIO",
+}
+
+local createRoutePoint = function(point, defaultAction)
+  local pos = coord.LLtoLO(point.position.lat, point.position.lon)
+  local routePoint = {
+    x = pos.x,
+    y = pos.z,
+    alt = point.position.alt,
+    type = pointTypes[point.type] or "Turning Point",
+    eta = 0,
+    eta_locked = false,
+    alt_type = altitudeTypes[point.altitudeType] or "BARO",
+    formation_template = "",
+    speed = point.speed,
+    speed_locked = true,
+    action = point.action ~= "" and point.action or defaultAction,
+    name = point.name,
+    task = GRPC.importers.comboTask(point.tasks)
+  }
+
+  if point.form ~= nil and point.form ~= "" then
+    routePoint.form = point.form
+  end
+  if point.airdromeId ~= nil then
+    routePoint.airdromeId = point.airdromeId
+  end
+  if point.linkUnitId ~= nil then
+    routePoint.linkUnit = point.linkUnitId
+    routePoint.helipadId = point.linkUnitId
+  end
+
+  return routePoint
+end
+
+local createAircraftRoute = function(waypoints)
+  local points = {}
+  for _, point in ipairs(waypoints) do
+    table.insert(points, createRoutePoint(point, aircraftActions[point.type] or "Turning Point"))
+  end
+
+  return {points = points}
+end
+
+local createCallsign = function(callsign)
+  if callsign == nil or callsign.callsign == nil then
+    return nil
+  end
+
+  if callsign.callsign.numeric ~= nil then
+    return callsign.callsign.numeric
+  end
+
+  local western = callsign.callsign.western
+  return {
+    [1] = western.name,
+    [2] = western.flight,
+    [3] = western.element,
+  }
+end
+
+local createPayload = function(payload)
+  if payload == nil then
+    return nil
+  end
+
+  local pylons = {}
+  for _, pylon in ipairs(payload.pylons or {}) do
+    pylons[pylon.number] = {CLSID = pylon.clsid}
+  end
+
+  return {
+    fuel = payload.fuel,
+    flare = payload.flare,
+    chaff = payload.chaff,
+    gun = payload.gun or 100,
+    pylons = pylons,
+  }
+end
+
+local createAircraftUnitsTemplate = function(unitListTemplate, route)
+  local units = {}
+  local start = route.points[1]
+
+  for i, unitTemplate in ipairs(unitListTemplate) do
+    local unit = {
+      name = unitTemplate.name,
+      type = unitTemplate.type,
+      -- units without a position are lined up behind the first waypoint
+      x = start.x - (i - 1) * 50,
+      y = start.y,
+      alt = start.alt,
+      alt_type = start.alt_type,
+      speed = start.speed,
+      heading = math.rad(unitTemplate.heading or 0),
+      skill = skill[unitTemplate.skill],
+      livery_id = unitTemplate.livery,
+      onboard_num = unitTemplate.onboardNum,
+      callsign = createCallsign(unitTemplate.callsign),
+      payload = createPayload(unitTemplate.payload),
+      parking = unitTemplate.parking,
+    }
+    if unitTemplate.position ~= nil then
+      local pos = toVec3(unitTemplate.position)
+      unit.x = pos.x
+      unit.y = pos.z
+      unit.alt = pos.y
+    end
+    if unitTemplate.unitId ~= nil then
+      unit.unitId = unitTemplate.unitId
+    end
+    table.insert(units, unit)
+  end
+
+  return units
+end
+
+local createAircraftGroupTemplate = function(groupTemplate)
+  local route = createAircraftRoute(groupTemplate.waypoints)
+  local groupTable = {
+    name = groupTemplate.name,
+    task = groupTemplate.task ~= "" and groupTemplate.task or "Nothing",
+    route = route,
+    units = createAircraftUnitsTemplate(groupTemplate.units, route),
+    uncontrolled = groupTemplate.uncontrolled,
+    hidden = groupTemplate.hidden,
+    lateActivation = groupTemplate.lateActivation,
+    x = route.points[1].x,
+    y = route.points[1].y,
+  }
+
+  if groupTemplate.groupId ~= nil then
+    groupTable.groupId = groupTemplate.groupId
+  end
+  if groupTemplate.startTime ~= nil and groupTemplate.startTime > 0 then
+    groupTable.start_time = groupTemplate.startTime
+  end
+  if groupTemplate.frequency ~= nil then
+    groupTable.communication = true
+    groupTable.frequency = groupTemplate.frequency / 1000000 -- DCS expects MHz
+    groupTable.modulation = groupTemplate.modulation
+  end
+
+  return groupTable
+end
+
+local createShipGroupTemplate = function(groupTemplate)
+  local pos = coord.LLtoLO(groupTemplate.position.lat, groupTemplate.position.lon)
+  local route = {
+    points = {
+      createRoutePoint({
+        position = groupTemplate.position,
+        type = 3, -- POINT_TYPE_TURNING_POINT
+        speed = 0,
+        action = "",
+      }, "Turning Point")
+    }
+  }
+  for _, point in ipairs(groupTemplate.waypoints or {}) do
+    table.insert(route.points, createRoutePoint(point, "Turning Point"))
+  end
+
+  local groupTable = {
+    name = groupTemplate.name,
+    route = route,
+    units = createShipUnitsTemplate(groupTemplate.units),
+    uncontrollable = groupTemplate.uncontrollable,
+    visible = groupTemplate.visible,
+    hidden = groupTemplate.hidden,
+    lateActivation = groupTemplate.lateActivation,
+    x = pos.x,
+    y = pos.z,
+  }
+
+  if groupTemplate.groupId ~= nil then
+    groupTable.groupId = groupTemplate.groupId
+  end
+  if groupTemplate.startTime ~= nil and groupTemplate.startTime > 0 then
+    groupTable.start_time = groupTemplate.startTime
+  end
+
+  return groupTable
+end
+
 local createGroundGroupTemplate = function(groupTemplate)
   local pos = coord.LLtoLO(groupTemplate.position.lat, groupTemplate.position.lon)
 
@@ -67,36 +298,35 @@ local createGroundGroupTemplate = function(groupTemplate)
           formation_template = "",
           speed = 0,
           action = "Off Road",
-          task = {
-            id = "ComboTask",
-            params = {
-                tasks = {}
-            }
-          }
+          task = GRPC.importers.comboTask(groupTemplate.tasks)
         }
       }
     },
-    task = "Ground Nothing",
+    task = groupTemplate.task ~= "" and groupTemplate.task or "Ground Nothing",
     taskSelected = true,
     tasks = {},
-    uncontrollable = false,
+    uncontrollable = groupTemplate.uncontrollable,
     units = createGroundUnitsTemplate(groupTemplate.units),
     visible = false,
     x = pos.x,
     y = pos.z
   }
 
-  if groupTemplate.group_id ~= nil then
-    groupTable['groupId'] = groupTemplate.group_id
+  for _, point in ipairs(groupTemplate.waypoints or {}) do
+    table.insert(groupTable.route.points, createRoutePoint(point, "Off Road"))
+  end
+
+  if groupTemplate.groupId ~= nil then
+    groupTable['groupId'] = groupTemplate.groupId
   end
   if groupTemplate.hidden ~= nil then
     groupTable['hidden'] = groupTemplate.hidden
   end
-  if groupTemplate.late_activation ~= nil then
-    groupTable['lateActivation'] = groupTemplate.late_activation
+  if groupTemplate.lateActivation ~= nil then
+    groupTable['lateActivation'] = groupTemplate.lateActivation
   end
-  if groupTemplate.start_time ~= nil and groupTemplate.start_time > 0 then
-    groupTable['start_time'] = groupTemplate.start_time
+  if groupTemplate.startTime ~= nil and groupTemplate.startTime > 0 then
+    groupTable['start_time'] = groupTemplate.startTime
   end
   if groupTemplate.visible ~= nil then
     groupTable['visible'] = groupTemplate.visible
@@ -105,19 +335,39 @@ local createGroundGroupTemplate = function(groupTemplate)
   return groupTable
 end
 
+-- The template is validated by the server before being sent to the mission
 GRPC.methods.addGroup = function(params)
-  if params.groupCategory == 0 then
-    return GRPC.errorInvalidArgument("group category must be specified")
-  end
-  if params.country_id == 0 or params.country_id == 15 then
-    return GRPC.errorInvalidArgument("invalid country code")
+  local createTemplate, groupTemplate
+  if params.template.groundTemplate ~= nil then
+    createTemplate, groupTemplate = createGroundGroupTemplate, params.template.groundTemplate
+  elseif params.template.shipTemplate ~= nil then
+    createTemplate, groupTemplate = createShipGroupTemplate, params.template.shipTemplate
+  elseif params.template.helicopterTemplate ~= nil then
+    createTemplate, groupTemplate = createAircraftGroupTemplate, params.template.helicopterTemplate
+  elseif params.template.planeTemplate ~= nil then
+    createTemplate, groupTemplate = createAircraftGroupTemplate, params.template.planeTemplate
+  else
+    return GRPC.errorInvalidArgument("template must be specified")
   end
 
-  local template = createGroundGroupTemplate(params.template.groundTemplate)
+  -- fails with a GRPC error if a task of the route is not supported or references a group or
+  -- unit that doesn't exist, any other error is raised again
+  local ok, template = pcall(createTemplate, groupTemplate)
+  if not ok then
+    if type(template) == "table" and template.error ~= nil then
+      return template
+    end
+    error(template, 0)
+  end
 
   coalition.addGroup(params.country - 1, params.groupCategory - 1, template) -- Decrement for non zero-indexed gRPC enum
 
-  return GRPC.success({group = GRPC.exporters.group(Group.getByName(template.name))})
+  local group = Group.getByName(template.name)
+  if group == nil then
+    return GRPC.error("Failed to add group `" .. template.name .. "`")
+  end
+
+  return GRPC.success({group = GRPC.exporters.group(group)})
 end
 
 GRPC.methods.getStaticObjects = function(params)
@@ -91,4 +91,175 @@ GRPC.methods.getDetectedTargets = function(params)
   return GRPC.success({
     contacts = results
   })
-end
\ No newline at end of file
+end
+
+-- Resolve the group or unit referenced by the `name` oneof of a request.
+local function getObject(name)
+  if name == nil then
+    return nil, GRPC.errorInvalidArgument("No Group or Unit name provided")
+  end
+
+  if name.groupName then
+    local group = Group.getByName(name.groupName)
+    if group == nil then
+      return nil, GRPC.errorNotFound("Could not find group with name '" .. name.groupName .. "'")
+    end
+    return group
+  elseif name.unitName then
+    local unit = Unit.getByName(name.unitName)
+    if unit == nil then
+      return nil, GRPC.errorNotFound("Could not find unit with name '" .. name.unitName .. "'")
+    end
+    return unit
+  end
+
+  return nil, GRPC.errorInvalidArgument("No Group or Unit name provided")
+end
+
+-- The AI.Option table of the group or unit, groups and units share the
+-- category values AIRPLANE = 0, HELICOPTER = 1, GROUND = 2 and SHIP = 3.
+local optionCategories = {
+  [0] = "Air",
+  [1] = "Air",
+  [2] = "Ground",
+  [3] = "Naval",
+}
+
+local function getOptionCategory(obj)
+  local category
+  if obj.getUnits ~= nil then
+    category = obj:getCategory()
+  else
+    category = obj:getDesc().category
+  end
+  return optionCategories[category]
+end
+
+local function setTask(params, fn)
+  local obj, err = getObject(params.name)
+  if obj == nil then
+    return err
+  end
+
+  local task, taskErr = GRPC.importers.task(params.task)
+  if task == nil then
+    return taskErr
+  end
+
+  fn(obj:getController(), task)
+  return GRPC.success({})
+end
+
+GRPC.methods.setTask = function(params)
+  return setTask(params, function(controller, task) controller:setTask(task) end)
+end
+
+GRPC.methods.pushTask = function(params)
+  return setTask(params, function(controller, task) controller:pushTask(task) end)
+end
+
+GRPC.methods.popTask = function(params)
+  local obj, err = getObject(params.name)
+  if obj == nil then
+    return err
+  end
+
+  obj:getController():popTask()
+  return GRPC.success({})
+end
+
+GRPC.methods.resetTask = function(params)
+  local obj, err = getObject(params.name)
+  if obj == nil then
+    return err
+  end
+
+  obj:getController():resetTask()
+  return GRPC.success({})
+end
+
+GRPC.methods.hasTask = function(params)
+  local obj, err = getObject(params.name)
+  if obj == nil then
+    return err
+  end
+
+  return GRPC.success({hasTask = obj:getController():hasTask()})
+end
+
+GRPC.methods.setOption = function(params)
+  local obj, err = getObject(params.name)
+  if obj == nil then
+    return err
+  end
+
+  local category = getOptionCategory(obj)
+  if category == nil then
+    return GRPC.errorInvalidArgument("Options can only be set for aircraft, ground units and ships")
+  end
+
+  local option = params.option.option
+  local id, value
+  if option.roe ~= nil then
+    -- the Roe enum is offset by one to the AI.Option.*.val.ROE values
+    value = option.roe - 1
+    if category ~= "Air" and value < 2 then
+      return GRPC.errorInvalidArgument(
+        "ROE_WEAPON_FREE and ROE_OPEN_FIRE_WEAPON_FREE are only supported by aircraft"
+      )
+    end
+    id = AI.Option[category].id.ROE
+  elseif option.reactionOnThreat ~= nil then
+    if category ~= "Air" then
+      return GRPC.errorInvalidArgument("The reaction on threat can only be set for aircraft")
+    end
+    -- the ReactionOnThreat enum is offset by one to AI.Option.Air.val.REACTION_ON_THREAT
+    value = option.reactionOnThreat - 1
+    id = AI.Option.Air.id.REACTION_ON_THREAT
+  end
+
+  obj:getController():setOption(id, value)
+  return GRPC.success({})
+end
+
+GRPC.methods.setCommand = function(params)
+  local obj, err = getObject(params.name)
+  if obj == nil then
+    return err
+  end
+
+  local command = params.command.command
+  local dcsCommand
+  if command.eplrs ~= nil then
+    dcsCommand = {id = "EPLRS", params = {value = command.eplrs}}
+    if obj.getUnits ~= nil then
+      dcsCommand.params.groupId = obj:getID()
+    end
+  elseif command.setFrequency ~= nil then
+    dcsCommand = {
+      id = "SetFrequency",
+      params = {
+        frequency = command.setFrequency.frequency,
+        modulation = command.setFrequency.modulation,
+        power = command.setFrequency.power or 10,
+      },
+    }
+  elseif command.setInvisible ~= nil then
+    dcsCommand = {id = "SetInvisible", params = {value = command.setInvisible}}
+  elseif command.setImmortal ~= nil then
+    dcsCommand = {id = "SetImmortal", params = {value = command.setImmortal}}
+  end
+
+  obj:getController():setCommand(dcsCommand)
+  return GRPC.success({})
+end
+
+GRPC.methods.setOnOff = function(params)
+  local group = Group.getByName(params.groupName)
+  if group == nil then
+    return GRPC.errorNotFound("Could not find group with name '" .. params.groupName .. "'")
+  end
+
+  group:getController():setOnOff(params.on)
+  return GRPC.success({})
+end
@@ -31,3 +31,56 @@ GRPC.methods.missionEval = function(params)
 
     return GRPC.success(net.lua2json(result))
 end
+
+-- Methods registered by mission scripts via `GRPC.methods.register`, callable via
+-- `CustomService.Call`. They are kept separately from the built-in methods, so that a mission
+-- script can't accidentally override one of them.
+local customMethods = {}
+
+--- Register the function `fn` as custom method `name`. The function receives the params of the
+--- call as table and has to return a table (or nil), which is sent back as result.
+GRPC.methods.register = function(name, fn, description)
+    if type(name) ~= "string" or name == "" then
+        error("expected a non-empty string as method name, got "..tostring(name))
+    end
+    if type(fn) ~= "function" then
+        error("expected a function for method `"..name.."`, got "..type(fn))
+    end
+
+    customMethods[name] = {
+        fn = fn,
+        description = description,
+    }
+end
+
+GRPC.methods.callCustomMethod = function(params)
+    local method = customMethods[params.name]
+    if method == nil then
+        return GRPC.errorNotFound("custom method `"..params.name.."` is not registered")
+    end
+
+    local result = method.fn(params.params or {})
+    if result ~= nil and type(result) ~= "table" then
+        return GRPC.error("custom method `"..params.name.."` returned a "..type(result)..
+            " instead of a table")
+    end
+
+    return GRPC.success({
+        result = result or {}
+    })
+end
+
+GRPC.methods.listCustomMethods = function()
+    local methods = {}
+    for name, method in pairs(customMethods) do
+        table.insert(methods, {
+            name = name,
+            description = method.description,
+        })
+    end
+    table.sort(methods, function(a, b) return a.name < b.name end)
+
+    return GRPC.success({
+        methods = methods
+    })
+end
@@ -47,4 +47,118 @@ GRPC.methods.groupDestroy = function(params)
   group:destroy()
 
   return GRPC.success({})
-end
\ No newline at end of file
+end
+
+local function getGroup(groupName)
+  -- https://wiki.hoggitworld.com/view/DCS_func_getByName
+  local group = Group.getByName(groupName)
+  if group == nil then
+    return nil, GRPC.errorNotFound("group `" .. tostring(groupName) .. "` does not exist")
+  end
+
+  return group
+end
+
+GRPC.methods.groupGet = function(params)
+  local group, err = getGroup(params.groupName)
+  if group == nil then
+    return err
+  end
+
+  -- groups don't know their country, all of their units share it though
+  local country = 0
+  local unit = group:getUnit(1)
+  if unit ~= nil then
+    country = unit:getCountry() + 1 -- Increment for non zero-indexed gRPC enum
+  end
+
+  return GRPC.success({
+    group = GRPC.exporters.group(group),
+    country = country,
+    size = group:getSize(),
+    initialSize = group:getInitialSize(),
+  })
+end
+
+GRPC.methods.groupGetSize = function(params)
+  local group, err = getGroup(params.groupName)
+  if group == nil then
+    return err
+  end
+
+  -- https://wiki.hoggitworld.com/view/DCS_func_getSize
+  return GRPC.success({size = group:getSize()})
+end
+
+GRPC.methods.groupGetInitialSize = function(params)
+  local group, err = getGroup(params.groupName)
+  if group == nil then
+    return err
+  end
+
+  -- https://wiki.hoggitworld.com/view/DCS_func_getInitialSize
+  return GRPC.success({initialSize = group:getInitialSize()})
+end
+
+GRPC.methods.groupGetCategory = function(params)
+  local group, err = getGroup(params.groupName)
+  if group == nil then
+    return err
+  end
+
+  return GRPC.success({category = group:getCategory() + 1}) -- Increment for non zero-indexed gRPC enum
+end
+
+GRPC.methods.groupGetController = function(params)
+  local group, err = getGroup(params.groupName)
+  if group == nil then
+    return err
+  end
+
+  local controller = group:getController()
+  return GRPC.success({
+    hasTask = controller:hasTask(),
+    detectedTargets = #(controller:getDetectedTargets() or {}),
+  })
+end
+
+local function stopRoute(params, stop)
+  local group, err = getGroup(params.groupName)
+  if group == nil then
+    return err
+  end
+
+  -- https://wiki.hoggitworld.com/view/DCS_command_stopRoute
+  group:getController():setCommand({id = "StopRoute", params = {value = stop}})
+  return GRPC.success({})
+end
+
+GRPC.methods.groupStopMoving = function(params)
+  return stopRoute(params, true)
+end
+
+GRPC.methods.groupContinueMoving = function(params)
+  return stopRoute(params, false)
+end
+
+GRPC.methods.groupSetAiOnOff = function(params)
+  local group, err = getGroup(params.groupName)
+  if group == nil then
+    return err
+  end
+
+  -- https://wiki.hoggitworld.com/view/DCS_func_setOnOff
+  group:getController():setOnOff(params.on)
+  return GRPC.success({})
+end
+
+GRPC.methods.groupDeactivate = function(params)
+  local group, err = getGroup(params.groupName)
+  if group == nil then
+    return err
+  end
+
+  -- https://wiki.hoggitworld.com/view/DCS_func_deactivateGroup
+  trigger.action.deactivateGroup(group)
+  return GRPC.success({})
+end
@@ -132,3 +132,117 @@ GRPC.methods.getBallisticsCount = function()
   return GRPC.success({count = count})
 end
 
+GRPC.methods.getModelTime = function()
+  -- https://wiki.hoggitworld.com/view/DCS_func_getModelTime
+  return GRPC.success({time = DCS.getModelTime()})
+end
+
+GRPC.methods.getPlayerStat = function(params)
+  if params.stat == 0 then
+    return GRPC.errorInvalidArgument("stat must be specified")
+  end
+
+  if not net.get_player_info(params.playerId, "id") then
+    return GRPC.errorNotFound("Could not find player with the ID of " .. params.playerId)
+  end
+
+  -- the PlayerStat enum is offset by one to the `net.PS_*` constants
+  return GRPC.success({value = net.get_stat(params.playerId, params.stat - 1)})
+end
+
+GRPC.methods.getMyPlayerId = function()
+  return GRPC.success({playerId = net.get_my_player_id()})
+end
+
+GRPC.methods.getServerId = function()
+  return GRPC.success({playerId = net.get_server_id()})
+end
+
+GRPC.methods.setSlot = function(params)
+  if params.coalition == 0 then
+    return GRPC.errorInvalidArgument("Cannot set a slot in the COALITION_ALL")
+  end
+
+  net.set_slot(params.coalition - 1, params.slotId) -- common.Coalition enum offset
+  return GRPC.success({})
+end
+
+local slotSides = {
+  [2] = "red", -- COALITION_RED
+  [3] = "blue", -- COALITION_BLUE
+}
+
+GRPC.methods.getAvailableSlots = function(params)
+  -- https://wiki.hoggitworld.com/view/DCS_func_getAvailableSlots
+  local side = slotSides[params.coalition]
+  if not side then
+    return GRPC.errorInvalidArgument("Slots are only available for COALITION_RED and COALITION_BLUE")
+  end
+
+  local slots = {}
+  local slotsById = {}
+  local seats = {}
+  for _, slot in ipairs(DCS.getAvailableSlots(side)) do
+    local slotId = tostring(slot.unitId)
+    -- additional seats of multicrew aircraft have ids like `12_2`
+    local unitId, place = slotId:match("^(%d+)_(%d+)$")
+    if unitId then
+      table.insert(seats, {
+        unitId = unitId,
+        seat = {
+          slotId = slotId,
+          role = slot.role or "",
+          place = tonumber(slot.multicrew_place or place),
+        },
+      })
+    else
+      local result = {
+        slotId = slotId,
+        type = slot.type or "",
+        role = slot.role or "",
+        groupName = slot.groupName or "",
+        groupSize = tonumber(slot.groupSize) or 0,
+        countryName = slot.countryName or "",
+        onboardNum = slot.onboard_num or "",
+        task = slot.task or "",
+        action = slot.action or "",
+        coalition = params.coalition,
+        seats = {},
+      }
+      table.insert(slots, result)
+      slotsById[slotId] = result
+    end
+  end
+
+  for _, seat in ipairs(seats) do
+    local slot = slotsById[seat.unitId]
+    if slot then
+      table.insert(slot.seats, seat.seat)
+    end
+  end
+
+  return GRPC.success({slots = slots})
+end
+
+GRPC.methods.getAvailableCoalitions = function()
+  -- https://wiki.hoggitworld.com/view/DCS_func_getAvailableCoalitions
+  local coalitions = {}
+  for side in pairs(DCS.getAvailableCoalitions()) do
+    table.insert(coalitions, side + 1) -- common.Coalition enum offset
+  end
+  table.sort(coalitions)
+
+  return GRPC.success({coalitions = coalitions})
+end
+
+GRPC.methods.getCurrentMission = function()
+  -- https://wiki.hoggitworld.com/view/DCS_func_getCurrentMission
+  -- number keys (e.g. of sparse arrays) are converted to strings by the server
+  return GRPC.success({mission = DCS.getCurrentMission()})
+end
+
+GRPC.methods.getMissionOptions = function()
+  -- https://wiki.hoggitworld.com/view/DCS_func_getMissionOptions
+  -- number keys (e.g. of sparse arrays) are converted to strings by the server
+  return GRPC.success({options = DCS.getMissionOptions()})
+end
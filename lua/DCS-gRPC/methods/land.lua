@@ -0,0 +1,164 @@
+--
+-- RPC land actions
+-- https://wiki.hoggitworld.com/view/DCS_singleton_land
+--
+
+local function toVec3(position)
+  return coord.LLtoLO(position.lat, position.lon, position.alt)
+end
+
+local function toVec2(position)
+  local point = toVec3(position)
+  return {x = point.x, y = point.z}
+end
+
+local function getSurfaceHeightWithSeabed(position)
+  local height, depth = land.getSurfaceHeightWithSeabed(toVec2(position))
+  return {height = height, depth = depth}
+end
+
+local function isVisible(params)
+  return land.isVisible(toVec3(params.from), toVec3(params.to))
+end
+
+local function getIntersectionPoint(params)
+  local origin = toVec3(params.origin)
+  local target = toVec3(params.target)
+  local delta = {x = target.x - origin.x, y = target.y - origin.y, z = target.z - origin.z}
+  local length = math.sqrt(delta.x * delta.x + delta.y * delta.y + delta.z * delta.z)
+  if length == 0 then
+    return nil, "origin and target must not be the same position"
+  end
+
+  local direction = {x = delta.x / length, y = delta.y / length, z = delta.z / length}
+  local point = land.getIP(origin, direction, params.distance or length)
+  if point == nil then
+    return {}
+  end
+
+  return {position = GRPC.exporters.position(point)}
+end
+
+-- Apply `fn` to each item and collect the results.
+local function batch(items, fn)
+  local results = {}
+  for i, item in ipairs(items) do
+    results[i] = fn(item)
+  end
+  return results
+end
+
+GRPC.methods.getHeight = function(params)
+  return GRPC.success({height = land.getHeight(toVec2(params.position))})
+end
+
+GRPC.methods.batchGetHeight = function(params)
+  return GRPC.success({
+    heights = batch(params.positions, function(position) return land.getHeight(toVec2(position)) end)
+  })
+end
+
+GRPC.methods.getSurfaceHeightWithSeabed = function(params)
+  return GRPC.success(getSurfaceHeightWithSeabed(params.position))
+end
+
+GRPC.methods.batchGetSurfaceHeightWithSeabed = function(params)
+  return GRPC.success({results = batch(params.positions, getSurfaceHeightWithSeabed)})
+end
+
+GRPC.methods.getSurfaceType = function(params)
+  -- the values of land.SurfaceType match the SurfaceType enum
+  return GRPC.success({surfaceType = land.getSurfaceType(toVec2(params.position))})
+end
+
+GRPC.methods.batchGetSurfaceType = function(params)
+  return GRPC.success({
+    surfaceTypes = batch(params.positions, function(position) return land.getSurfaceType(toVec2(position)) end)
+  })
+end
+
+GRPC.methods.isVisible = function(params)
+  return GRPC.success({visible = isVisible(params)})
+end
+
+GRPC.methods.batchIsVisible = function(params)
+  return GRPC.success({visible = batch(params.pairs, isVisible)})
+end
+
+GRPC.methods.getIntersectionPoint = function(params)
+  local result, err = getIntersectionPoint(params)
+  if result == nil then
+    return GRPC.errorInvalidArgument(err)
+  end
+
+  return GRPC.success(result)
+end
+
+GRPC.methods.batchGetIntersectionPoint = function(params)
+  local results = {}
+  for i, ray in ipairs(params.rays) do
+    local result, err = getIntersectionPoint(ray)
+    if result == nil then
+      return GRPC.errorInvalidArgument("ray " .. i .. ": " .. err)
+    end
+    results[i] = result
+  end
+
+  return GRPC.success({results = results})
+end
+
+GRPC.methods.getProfile = function(params)
+  local positions = {}
+  for i, point in ipairs(land.profile(toVec3(params.from), toVec3(params.to))) do
+    positions[i] = GRPC.exporters.position(point)
+  end
+
+  return GRPC.success({positions = positions})
+end
+
+local roadTypes = {
+  [1] = "roads", -- ROAD_TYPE_ROADS
+  [2] = "railroads", -- ROAD_TYPE_RAILROADS
+}
+
+-- Convert a Vec2 to a position on the ground.
+local function groundPosition(x, y)
+  return GRPC.exporters.position({x = x, y = land.getHeight({x = x, y = y}), z = y})
+end
+
+GRPC.methods.getClosestPointOnRoads = function(params)
+  local roadType = roadTypes[params.roadType]
+  if roadType == nil then
+    return GRPC.errorInvalidArgument("road type must be specified")
+  end
+
+  local point = toVec2(params.position)
+  local x, y = land.getClosestPointOnRoads(roadType, point.x, point.y)
+  if x == nil then
+    return GRPC.errorNotFound("no " .. roadType .. " found")
+  end
+
+  return GRPC.success({position = groundPosition(x, y)})
+end
+
+GRPC.methods.findPathOnRoads = function(params)
+  local roadType = roadTypes[params.roadType]
+  if roadType == nil then
+    return GRPC.errorInvalidArgument("road type must be specified")
+  end
+
+  local from = toVec2(params.from)
+  local to = toVec2(params.to)
+  local path = {}
+  local length = 0
+  local previous = nil
+  for i, point in ipairs(land.findPathOnRoads(roadType, from.x, from.y, to.x, to.y) or {}) do
+    path[i] = groundPosition(point.x, point.y)
+    if previous ~= nil then
+      length = length + math.sqrt((point.x - previous.x) ^ 2 + (point.y - previous.y) ^ 2)
+    end
+    previous = point
+  end
+
+  return GRPC.success({path = path, length = length})
+end
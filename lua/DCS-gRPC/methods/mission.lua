@@ -334,6 +334,8 @@ GRPC.onDcsEvent = function(event)
     }
 
   elseif event.id == world.event.S_EVENT_MARK_REMOVED then
+    GRPC.onMarkRemoved(event.idx)
+
     local payload = {
       type = "markRemove",
       initiator = {initiator = typed_exporter(event.initiator)},
@@ -434,6 +436,180 @@ GRPC.onDcsEvent = function(event)
   end
 end
 
+-- Registry of the command menus added through DCS-gRPC (per scope), used to list them via
+-- `ListCommands`. DCS itself doesn't provide a way to query the F10 menu.
+
+local function newMenu()
+  return {
+    items = {},
+    otherCommands = {},
+  }
+end
+
+local menus = {
+  mission = newMenu(),
+  coalitions = {}, -- by gRPC coalition
+  groups = {}, -- by group name
+}
+
+local function coalitionMenu(coalition)
+  if menus.coalitions[coalition] == nil then
+    menus.coalitions[coalition] = newMenu()
+  end
+  return menus.coalitions[coalition]
+end
+
+local function groupMenu(groupName)
+  if menus.groups[groupName] == nil then
+    menus.groups[groupName] = newMenu()
+  end
+  return menus.groups[groupName]
+end
+
+-- Forget the menus of groups that don't exist anymore, as DCS removes their menus together
+-- with the group.
+local function pruneGroupMenus()
+  for groupName in pairs(menus.groups) do
+    local group = Group.getByName(groupName)
+    if group == nil or not group:isExist() then
+      menus.groups[groupName] = nil
+    end
+  end
+end
+
+local function indexOf(list, name)
+  for i, item in ipairs(list) do
+    if item.name == name then
+      return i
+    end
+  end
+  return nil
+end
+
+-- Find the children of the submenu at the given path (or the first level for an empty path).
+local function findChildren(items, path, len)
+  for i = 1, len do
+    local idx = indexOf(items, path[i])
+    if idx == nil or not items[idx].subMenu then
+      return nil
+    end
+    items = items[idx].children
+  end
+  return items
+end
+
+-- Like `findChildren`, but registers the submenus of the path that are missing (e.g. because
+-- they were added by a mission script instead of through DCS-gRPC).
+local function findOrAddChildren(items, path, len)
+  for i = 1, len do
+    local idx = indexOf(items, path[i])
+    if idx == nil then
+      idx = #items + 1
+      items[idx] = {
+        name = path[i],
+        path = { unpack(path, 1, i) },
+        subMenu = true,
+        children = {},
+      }
+    else
+      items[idx].subMenu = true
+    end
+    items = items[idx].children
+  end
+  return items
+end
+
+local function registerItem(menu, path, subMenu, details)
+  if path == nil or #path == 0 then
+    return
+  end
+
+  local items = findOrAddChildren(menu.items, path, #path - 1)
+
+  local item = {
+    name = path[#path],
+    path = path,
+    subMenu = subMenu,
+    details = details,
+    children = {},
+  }
+  local idx = indexOf(items, item.name)
+  if idx == nil then
+    items[#items + 1] = item
+  else
+    items[idx] = item
+  end
+end
+
+local function unregisterItem(menu, path)
+  if path == nil or #path == 0 then
+    -- removing the root removes all items
+    menu.items = {}
+    return
+  end
+
+  local items = findChildren(menu.items, path, #path - 1)
+  local idx = items and indexOf(items, path[#path])
+  if idx ~= nil then
+    table.remove(items, idx)
+  end
+end
+
+local function exportItems(items)
+  local result = {}
+  for i, item in ipairs(items) do
+    result[i] = {
+      name = item.name,
+      path = item.path,
+      subMenu = item.subMenu,
+      details = item.details,
+      children = exportItems(item.children),
+    }
+  end
+  return result
+end
+
+local function exportMenu(menu)
+  local otherCommands = {}
+  for i, command in ipairs(menu.otherCommands) do
+    otherCommands[i] = {
+      name = command.name,
+      details = command.details,
+    }
+  end
+
+  return {
+    items = exportItems(menu.items),
+    otherCommands = otherCommands,
+  }
+end
+
+GRPC.methods.listCommands = function()
+  pruneGroupMenus()
+
+  local coalitions = {}
+  for coalition, menu in pairs(menus.coalitions) do
+    coalitions[#coalitions + 1] = {
+      coalition = coalition,
+      menu = exportMenu(menu),
+    }
+  end
+
+  local groups = {}
+  for groupName, menu in pairs(menus.groups) do
+    groups[#groups + 1] = {
+      groupName = groupName,
+      menu = exportMenu(menu),
+    }
+  end
+
+  return GRPC.success({
+    mission = exportMenu(menus.mission),
+    coalitions = coalitions,
+    groups = groups,
+  })
+end
+
 -- Mission Level Commands
 
 local function missionCommandCallback(details)
@@ -450,19 +626,26 @@ end
 
 
 GRPC.methods.addMissionCommand = function(params)
+  local path = missionCommands.addCommand(params.name, params.path, missionCommandCallback, params.details)
+  registerItem(menus.mission, path, false, params.details)
+
   return GRPC.success({
-    path = missionCommands.addCommand(params.name, params.path, missionCommandCallback, params.details)
+    path = path
   })
 end
 
 GRPC.methods.addMissionCommandSubMenu = function(params)
+  local path = missionCommands.addSubMenu(params.name, params.path)
+  registerItem(menus.mission, path, true)
+
   return GRPC.success({
-    path = missionCommands.addSubMenu(params.name, params.path)
+    path = path
   })
 end
 
 GRPC.methods.removeMissionCommandItem = function(params)
   missionCommands.removeItem(params.path)
+  unregisterItem(menus.mission, params.path)
   return GRPC.success({})
 end
 
@@ -482,23 +665,30 @@ local function coalitionCommandCallback(params)
 end
 
 GRPC.methods.addCoalitionCommand = function(params)
+  -- Decrement coalition for non zero-indexed gRPC enum
+  local path = missionCommands.addCommandForCoalition(params.coalition - 1, params.name, params.path,
+    coalitionCommandCallback, params)
+  registerItem(coalitionMenu(params.coalition), path, false, params.details)
+
   return GRPC.success({
-    -- Decrement coalition for non zero-indexed gRPC enum
-    path = missionCommands.addCommandForCoalition(params.coalition - 1, params.name, params.path,
-      coalitionCommandCallback, params)
+    path = path
   })
 end
 
 GRPC.methods.addCoalitionCommandSubMenu = function(params)
+  -- Decrement coalition for non zero-indexed gRPC enum
+  local path = missionCommands.addSubMenuForCoalition(params.coalition - 1, params.name, params.path)
+  registerItem(coalitionMenu(params.coalition), path, true)
+
   return GRPC.success({
-    -- Decrement coalition for non zero-indexed gRPC enum
-    path = missionCommands.addSubMenuForCoalition(params.coalition - 1, params.name, params.path)
+    path = path
   })
 end
 
 GRPC.methods.removeCoalitionCommandItem = function(params)
   -- Decrement coalition for non zero-indexed gRPC enum
   missionCommands.removeItemForCoalition(params.coalition - 1, params.path)
+  unregisterItem(coalitionMenu(params.coalition), params.path)
   return GRPC.success({})
 end
 
@@ -524,9 +714,12 @@ GRPC.methods.addGroupCommand = function(params)
   end
   params['group'] = group
 
+  local path = missionCommands.addCommandForGroup(group:getID(), params.name, params.path,
+    groupCommandCallback, params)
+  registerItem(groupMenu(params.groupName), path, false, params.details)
+
   return GRPC.success({
-    path = missionCommands.addCommandForGroup(group:getID(), params.name, params.path,
-      groupCommandCallback, params)
+    path = path
   })
 end
 
@@ -536,8 +729,11 @@ GRPC.methods.addGroupCommandSubMenu = function(params)
     return GRPC.errorNotFound("group does not exist")
   end
 
+  local path = missionCommands.addSubMenuForGroup(group:getID(), params.name, params.path)
+  registerItem(groupMenu(params.groupName), path, true)
+
   return GRPC.success({
-    path = missionCommands.addSubMenuForGroup(group:getID(), params.name, params.path)
+    path = path
   })
 end
 
@@ -548,5 +744,159 @@ GRPC.methods.removeGroupCommandItem = function(params)
   end
 
   missionCommands.removeItemForGroup(group:getID(), params.path)
+  unregisterItem(groupMenu(params.groupName), params.path)
+  return GRPC.success({})
+end
+
+-- Other Commands
+--
+-- Instead of calling a function, "Other" commands set a user flag when selected. Each command
+-- gets its own flag, which is polled to emit the same events as for the commands above.
+
+local OTHER_COMMAND_POLL_INTERVAL = 1
+
+local otherCommandFlagId = 0
+local pollingOtherCommands = false
+
+local function hasOtherCommands()
+  if #menus.mission.otherCommands > 0 then
+    return true
+  end
+  for _, scope in ipairs({ menus.coalitions, menus.groups }) do
+    for _, menu in pairs(scope) do
+      if #menu.otherCommands > 0 then
+        return true
+      end
+    end
+  end
+  return false
+end
+
+-- Call `emit` for all commands of the menu that got selected since the last poll.
+local function pollMenu(menu, emit)
+  for _, command in ipairs(menu.otherCommands) do
+    if trigger.misc.getUserFlag(command.flag) ~= 0 then
+      trigger.action.setUserFlag(command.flag, 0)
+      emit(command)
+    end
+  end
+end
+
+local function pollOtherCommands()
+  pollMenu(menus.mission, function(command)
+    missionCommandCallback(command.details)
+  end)
+
+  for coalition, menu in pairs(menus.coalitions) do
+    pollMenu(menu, function(command)
+      coalitionCommandCallback({ coalition = coalition, details = command.details })
+    end)
+  end
+
+  pruneGroupMenus()
+  for groupName, menu in pairs(menus.groups) do
+    pollMenu(menu, function(command)
+      groupCommandCallback({ group = Group.getByName(groupName), details = command.details })
+    end)
+  end
+end
+
+local function startPollingOtherCommands()
+  if pollingOtherCommands then
+    return
+  end
+  pollingOtherCommands = true
+
+  timer.scheduleFunction(function()
+    local ok, err = pcall(pollOtherCommands)
+    if not ok then
+      GRPC.logError("Error polling other commands: "..tostring(err))
+    end
+
+    if not hasOtherCommands() then
+      pollingOtherCommands = false
+      return nil
+    end
+    return timer.getTime() + OTHER_COMMAND_POLL_INTERVAL
+  end, nil, timer.getTime() + OTHER_COMMAND_POLL_INTERVAL)
+end
+
+local function addOtherCommand(menu, params, add)
+  if indexOf(menu.otherCommands, params.name) ~= nil then
+    return GRPC.errorAlreadyExists("other command `" .. params.name .. "` already exists")
+  end
+
+  otherCommandFlagId = otherCommandFlagId + 1
+  local flag = "dcs_grpc_other_command_" .. otherCommandFlagId
+  trigger.action.setUserFlag(flag, 0)
+  add(flag)
+
+  menu.otherCommands[#menu.otherCommands + 1] = {
+    name = params.name,
+    details = params.details,
+    flag = flag,
+  }
+  startPollingOtherCommands()
+
   return GRPC.success({})
 end
+
+local function removeOtherCommand(menu, params, remove)
+  local idx = indexOf(menu.otherCommands, params.name)
+  if idx == nil then
+    return GRPC.errorNotFound("other command `" .. params.name .. "` does not exist")
+  end
+
+  remove()
+  table.remove(menu.otherCommands, idx)
+
+  return GRPC.success({})
+end
+
+GRPC.methods.addMissionOtherCommand = function(params)
+  return addOtherCommand(menus.mission, params, function(flag)
+    trigger.action.addOtherCommand(params.name, flag, 1)
+  end)
+end
+
+GRPC.methods.removeMissionOtherCommand = function(params)
+  return removeOtherCommand(menus.mission, params, function()
+    trigger.action.removeOtherCommand(params.name)
+  end)
+end
+
+GRPC.methods.addCoalitionOtherCommand = function(params)
+  return addOtherCommand(coalitionMenu(params.coalition), params, function(flag)
+    -- Decrement coalition for non zero-indexed gRPC enum
+    trigger.action.addOtherCommandForCoalition(params.coalition - 1, params.name, flag, 1)
+  end)
+end
+
+GRPC.methods.removeCoalitionOtherCommand = function(params)
+  return removeOtherCommand(coalitionMenu(params.coalition), params, function()
+    -- Decrement coalition for non zero-indexed gRPC enum
+    trigger.action.removeOtherCommandForCoalition(params.coalition - 1, params.name)
+  end)
+end
+
+GRPC.methods.addGroupOtherCommand = function(params)
+  local group = Group.getByName(params.groupName)
+  if group == nil then
+    return GRPC.errorNotFound("group does not exist")
+  end
+
+  return addOtherCommand(groupMenu(params.groupName), params, function(flag)
+    trigger.action.addOtherCommandForGroup(group:getID(), params.name, flag, 1)
+  end)
+end
+
+GRPC.methods.removeGroupOtherCommand = function(params)
+  local group = Group.getByName(params.groupName)
+  if group == nil then
+    return GRPC.errorNotFound("group does not exist")
+  end
+
+  return removeOtherCommand(groupMenu(params.groupName), params, function()
+    trigger.action.removeOtherCommandForGroup(group:getID(), params.name)
+  end)
+end
@@ -52,6 +52,82 @@ GRPC.methods.outTextForUnit = function(params)
   return GRPC.success({})
 end
 
+GRPC.methods.outTextForCountry = function(params)
+  if params.country == 0 then
+    return GRPC.errorInvalidArgument("a specific country must be chosen")
+  end
+
+  -- Decrement for non zero-indexed gRPC enum
+  trigger.action.outTextForCountry(params.country - 1, params.text, params.displayTime, params.clearView)
+
+  return GRPC.success({})
+end
+
+-- Sound files given by their file name only are looked up in the default
+-- resource folder of the mission.
+local function soundFile(file)
+  if string.find(file, "[/\\]") then
+    return file
+  end
+  return "l10n/DEFAULT/" .. file
+end
+
+GRPC.methods.outSound = function(params)
+  trigger.action.outSound(soundFile(params.file))
+
+  return GRPC.success({})
+end
+
+GRPC.methods.outSoundForCoalition = function(params)
+  if params.coalition == 0 then
+    return GRPC.errorInvalidArgument("a specific coalition must be chosen")
+  end
+
+  -- Decrement for non zero-indexed gRPC enum
+  trigger.action.outSoundForCoalition(params.coalition - 1, soundFile(params.file))
+
+  return GRPC.success({})
+end
+
+GRPC.methods.outSoundForCountry = function(params)
+  if params.country == 0 then
+    return GRPC.errorInvalidArgument("a specific country must be chosen")
+  end
+
+  -- Decrement for non zero-indexed gRPC enum
+  trigger.action.outSoundForCountry(params.country - 1, soundFile(params.file))
+
+  return GRPC.success({})
+end
+
+GRPC.methods.outSoundForGroup = function(params)
+  trigger.action.outSoundForGroup(params.groupId, soundFile(params.file))
+
+  return GRPC.success({})
+end
+
+GRPC.methods.radioTransmission = function(params)
+  local point = coord.LLtoLO(params.position.lat, params.position.lon, params.position.alt)
+
+  trigger.action.radioTransmission(
+    soundFile(params.file),
+    point,
+    params.modulation,
+    params.loop,
+    params.frequency,
+    params.power or 100,
+    params.name
+  )
+
+  return GRPC.success({})
+end
+
+GRPC.methods.stopRadioTransmission = function(params)
+  trigger.action.stopRadioTransmission(params.name)
+
+  return GRPC.success({})
+end
+
 GRPC.methods.getUserFlag = function(params)
   return GRPC.success({
     value = trigger.misc.getUserFlag(params.flag),
@@ -97,8 +173,20 @@ GRPC.methods.markToGroup = function(params)
   })
 end
 
+-- Drawings created through the API by their id, for `listDrawings` and
+-- `clearDrawings`
+local drawings = {}
+
+-- Forget drawings that were removed from the F10 map (e.g. marks deleted by
+-- players), so that they are neither listed nor updated anymore. Called for
+-- each S_EVENT_MARK_REMOVED.
+GRPC.onMarkRemoved = function(idx)
+  drawings[idx] = nil
+end
+
 GRPC.methods.removeMark = function(params)
   trigger.action.removeMark(params.id)
+  drawings[params.id] = nil
 
   return GRPC.success({})
 end
@@ -132,6 +220,18 @@ GRPC.methods.markupToAll = function(params)
 
   trigger.action.markupToAll(params.shape, coalition, idx, unpack(packedParams))
 
+  drawings[idx] = {
+    id = idx,
+    shape = params.shape,
+    coalition = coalition + 1, -- Increment for non zero-indexed gRPC enum
+    points = params.points,
+    color = params.borderColor,
+    fillColor = params.fillColor,
+    lineType = params.lineType,
+    readOnly = params.readOnly,
+    message = params.message,
+  }
+
   return GRPC.success({
     id = idx
   })
@@ -149,6 +249,169 @@ GRPC.methods.markupToCoalition = function(params)
 end
 
 
+local function toVec3(position)
+  return coord.LLtoLO(position.lat, position.lon, position.alt)
+end
+
+local function toColor(color)
+  return {color.red, color.green, color.blue, color.alpha}
+end
+
+local transparent = {red = 0, green = 0, blue = 0, alpha = 0}
+
+-- Allocate an id, draw the drawing with it and add it to the registry. Values
+-- of the Shape enum match the shape ids of `markupToAll`.
+local function draw(shape, params, fn)
+  local idx = getMarkId()
+  local drawing = {
+    id = idx,
+    shape = shape,
+    coalition = params.coalition,
+    color = params.color,
+    fillColor = params.fillColor or transparent,
+    lineType = params.lineType or 0,
+    readOnly = params.readOnly,
+    message = params.message or "",
+  }
+  -- Decrement for non zero-indexed gRPC enum (COALITION_ALL becomes -1)
+  fn(params.coalition - 1, idx, drawing)
+  drawings[idx] = drawing
+
+  return GRPC.success({
+    id = idx
+  })
+end
+
+GRPC.methods.lineToAll = function(params)
+  return draw(1, params, function(coalition, idx, drawing)
+    drawing.points = {params.from, params.to}
+    trigger.action.lineToAll(coalition, idx, toVec3(params.from), toVec3(params.to), toColor(drawing.color),
+      drawing.lineType, drawing.readOnly, drawing.message)
+  end)
+end
+
+GRPC.methods.circleToAll = function(params)
+  return draw(2, params, function(coalition, idx, drawing)
+    drawing.points = {params.center}
+    drawing.radius = params.radius
+    trigger.action.circleToAll(coalition, idx, toVec3(params.center), params.radius, toColor(drawing.color),
+      toColor(drawing.fillColor), drawing.lineType, drawing.readOnly, drawing.message)
+  end)
+end
+
+GRPC.methods.rectToAll = function(params)
+  return draw(3, params, function(coalition, idx, drawing)
+    drawing.points = {params.from, params.to}
+    trigger.action.rectToAll(coalition, idx, toVec3(params.from), toVec3(params.to), toColor(drawing.color),
+      toColor(drawing.fillColor), drawing.lineType, drawing.readOnly, drawing.message)
+  end)
+end
+
+GRPC.methods.arrowToAll = function(params)
+  return draw(4, params, function(coalition, idx, drawing)
+    drawing.points = {params.from, params.to}
+    trigger.action.arrowToAll(coalition, idx, toVec3(params.from), toVec3(params.to), toColor(drawing.color),
+      toColor(drawing.fillColor), drawing.lineType, drawing.readOnly, drawing.message)
+  end)
+end
+
+GRPC.methods.textToAll = function(params)
+  return draw(5, params, function(coalition, idx, drawing)
+    drawing.points = {params.position}
+    drawing.text = params.text
+    drawing.fontSize = params.fontSize
+    trigger.action.textToAll(coalition, idx, toVec3(params.position), toColor(drawing.color),
+      toColor(drawing.fillColor), params.fontSize, drawing.readOnly, params.text)
+  end)
+end
+
+GRPC.methods.quadToAll = function(params)
+  return draw(6, params, function(coalition, idx, drawing)
+    local points = params.points
+    drawing.points = points
+    trigger.action.quadToAll(coalition, idx, toVec3(points[1]), toVec3(points[2]), toVec3(points[3]),
+      toVec3(points[4]), toColor(drawing.color), toColor(drawing.fillColor), drawing.lineType, drawing.readOnly,
+      drawing.message)
+  end)
+end
+
+GRPC.methods.updateDrawing = function(params)
+  local drawing = drawings[params.id]
+  if drawing == nil then
+    return GRPC.errorNotFound("drawing `" .. tostring(params.id) .. "` does not exist")
+  end
+
+  -- check everything before updating anything
+  if params.radius ~= nil and drawing.shape ~= 2 then
+    return GRPC.errorInvalidArgument("only circles have a radius")
+  end
+  if (params.text ~= nil or params.fontSize ~= nil) and drawing.shape ~= 5 then
+    return GRPC.errorInvalidArgument("only texts have a text and font size")
+  end
+  if params.to ~= nil and drawing.shape ~= 1 and drawing.shape ~= 3 and drawing.shape ~= 4 then
+    return GRPC.errorInvalidArgument("only lines, rectangles and arrows have a second point")
+  end
+
+  if params.color ~= nil then
+    trigger.action.setMarkupColor(params.id, toColor(params.color))
+    drawing.color = params.color
+  end
+  if params.fillColor ~= nil then
+    trigger.action.setMarkupColorFill(params.id, toColor(params.fillColor))
+    drawing.fillColor = params.fillColor
+  end
+  if params.lineType ~= nil then
+    trigger.action.setMarkupTypeLine(params.id, params.lineType)
+    drawing.lineType = params.lineType
+  end
+  if params.radius ~= nil then
+    trigger.action.setMarkupRadius(params.id, params.radius)
+    drawing.radius = params.radius
+  end
+  if params.text ~= nil then
+    trigger.action.setMarkupText(params.id, params.text)
+    drawing.text = params.text
+  end
+  if params.fontSize ~= nil then
+    trigger.action.setMarkupFontSize(params.id, params.fontSize)
+    drawing.fontSize = params.fontSize
+  end
+  if params.from ~= nil then
+    trigger.action.setMarkupPositionStart(params.id, toVec3(params.from))
+    drawing.points[1] = params.from
+  end
+  if params.to ~= nil then
+    trigger.action.setMarkupPositionEnd(params.id, toVec3(params.to))
+    drawing.points[2] = params.to
+  end
+
+  return GRPC.success({})
+end
+
+GRPC.methods.listDrawings = function(params)
+  local result = {}
+  for _, drawing in pairs(drawings) do
+    if params.coalition == 0 or drawing.coalition == 0 or drawing.coalition == params.coalition then
+      table.insert(result, drawing)
+    end
+  end
+  table.sort(result, function(a, b) return a.id < b.id end)
+
+  return GRPC.success({drawings = result})
+end
+
+GRPC.methods.clearDrawings = function()
+  local count = 0
+  for idx in pairs(drawings) do
+    trigger.action.removeMark(idx)
+    count = count + 1
+  end
+  drawings = {}
+
+  return GRPC.success({count = count})
+end
+
+
 GRPC.methods.explosion = function(params)
   local point = coord.LLtoLO(params.position.lat, params.position.lon, params.position.alt)
 
@@ -0,0 +1,76 @@
+--
+-- RPC trigger zone actions
+-- https://wiki.hoggitworld.com/view/DCS_func_getZone
+--
+-- luacheck: globals env land
+
+local env = env
+local land = land
+local GRPC = GRPC
+
+local ZONE_TYPE_QUAD = 2
+
+-- Map coordinates (x, y) of the mission file to a position at ground level.
+local function toPosition(x, y)
+  return GRPC.exporters.position({
+    x = x,
+    y = land.getHeight({ x = x, y = y }),
+    z = y,
+  })
+end
+
+local function exportZone(zone)
+  local result = {
+    id = zone.zoneId,
+    name = zone.name,
+    properties = {},
+  }
+
+  if zone.type == ZONE_TYPE_QUAD and zone.verticies then
+    local points = {}
+    for i, vertex in ipairs(zone.verticies) do
+      points[i] = toPosition(vertex.x, vertex.y)
+    end
+    result.shape = { quad = { points = points } }
+  else
+    result.shape = {
+      circle = {
+        center = toPosition(zone.x, zone.y),
+        radius = zone.radius,
+      },
+    }
+  end
+
+  for i, property in ipairs(zone.properties or {}) do
+    result.properties[i] = {
+      key = property.key,
+      value = tostring(property.value),
+    }
+  end
+
+  return result
+end
+
+local function getZones()
+  if env.mission.triggers and env.mission.triggers.zones then
+    return env.mission.triggers.zones
+  end
+  return {}
+end
+
+GRPC.methods.getZones = function()
+  local result = {}
+  for _, zone in ipairs(getZones()) do
+    result[#result + 1] = exportZone(zone)
+  end
+  return GRPC.success({zones = result})
+end
+
+GRPC.methods.getZone = function(params)
+  for _, zone in ipairs(getZones()) do
+    if zone.name == params.name then
+      return GRPC.success({zone = exportZone(zone)})
+    end
+  end
+  return GRPC.errorNotFound("Could not find zone with name '" .. params.name .. "'")
+end
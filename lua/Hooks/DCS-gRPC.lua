@@ -9,7 +9,15 @@ local function init()
       -- scaffold nested tables to allow direct assignment in config file
       tts = { provider = { gcloud = {}, aws = {}, azure = {}, win = {} } },
       srs = {},
-      auth = { tokens = {} }
+      auth = { tokens = {} },
+      grpcWeb = { corsOrigins = {} },
+      gateway = {},
+      websocket = {},
+      streams = {},
+      gatekeeper = {},
+      bans = {},
+      players = {},
+      zones = {}
     }
   end
 
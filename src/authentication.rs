@@ -1,40 +1,157 @@
 use tonic::body::Body;
-use tonic::codegen::http::Request;
+use tonic::codegen::http::{HeaderMap, Request, header};
 use tonic::{Status, async_trait};
 use tonic_middleware::RequestInterceptor;
 
 use crate::config::AuthConfig;
 
+/// The name of the authenticated client, added to the request extensions by the
+/// [AuthInterceptor].
+#[derive(Debug, Clone)]
+pub struct AuthenticatedClient(pub String);
+
+/// Services that can be used without authentication: health checks (e.g. by container
+/// orchestrators) and server reflection (e.g. by `grpcurl`), neither of which expose or change
+/// anything about the mission.
+const PUBLIC_SERVICES: &[&str] = &[
+    "/grpc.health.v1.Health/",
+    "/grpc.reflection.v1.ServerReflection/",
+    "/grpc.reflection.v1alpha.ServerReflection/",
+];
+
 #[derive(Clone)]
 pub struct AuthInterceptor {
     pub auth_config: AuthConfig,
 }
 
+impl AuthInterceptor {
+    /// Looks up the API key either in the `X-API-Key` header or as bearer token in the
+    /// `Authorization` header (the latter being the common way for gRPC-Web clients to send
+    /// credentials) and returns the name of the client it belongs to.
+    pub fn authenticate(&self, headers: &HeaderMap) -> Option<&str> {
+        let token = headers
+            .get("X-API-Key")
+            .and_then(|v| v.to_str().ok())
+            .or_else(|| {
+                headers
+                    .get(header::AUTHORIZATION)
+                    .and_then(|v| v.to_str().ok())
+                    .and_then(|v| v.strip_prefix("Bearer "))
+            })?;
+
+        self.auth_config
+            .tokens
+            .iter()
+            .find(|key| key.token == token)
+            .map(|key| key.client.as_str())
+    }
+}
+
 #[async_trait]
 impl RequestInterceptor for AuthInterceptor {
-    async fn intercept(&self, req: Request<Body>) -> Result<Request<Body>, Status> {
-        if !self.auth_config.enabled {
+    async fn intercept(&self, mut req: Request<Body>) -> Result<Request<Body>, Status> {
+        if !self.auth_config.enabled || is_public(req.uri().path()) {
             Ok(req)
         } else {
-            match req.headers().get("X-API-Key").map(|v| v.to_str()) {
-                Some(Ok(token)) => {
-                    let mut client: Option<&String> = None;
-                    for key in &self.auth_config.tokens {
-                        if key.token == token {
-                            client = Some(&key.client);
-                            break;
-                        }
-                    }
-
-                    if client.is_some() {
-                        log::debug!("Authenticated client: {}", client.unwrap());
-                        Ok(req)
-                    } else {
-                        Err(Status::unauthenticated("Unauthenticated"))
-                    }
+            match self.authenticate(req.headers()) {
+                Some(client) => {
+                    log::debug!("Authenticated client: {}", client);
+                    let client = AuthenticatedClient(client.to_string());
+                    req.extensions_mut().insert(client);
+                    Ok(req)
                 }
-                _ => Err(Status::unauthenticated("Unauthenticated")),
+                None => Err(Status::unauthenticated("Unauthenticated")),
             }
         }
     }
 }
+
+fn is_public(path: &str) -> bool {
+    PUBLIC_SERVICES
+        .iter()
+        .any(|service| path.starts_with(service))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::ApiKey;
+
+    use super::*;
+
+    fn interceptor() -> AuthInterceptor {
+        AuthInterceptor {
+            auth_config: AuthConfig {
+                enabled: true,
+                tokens: vec![ApiKey {
+                    client: "bot".to_string(),
+                    token: "secret".to_string(),
+                }],
+            },
+        }
+    }
+
+    fn request(path: &str, key: Option<&str>) -> Request<Body> {
+        let mut req = Request::builder().uri(path);
+        if let Some(key) = key {
+            req = req.header("X-API-Key", key);
+        }
+        req.body(Body::empty()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_requires_key() {
+        let interceptor = interceptor();
+        let path = "/dcs.mission.v0.MissionService/GetScenarioStartTime";
+
+        let err = interceptor
+            .intercept(request(path, None))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), tonic::Code::Unauthenticated);
+        let err = interceptor
+            .intercept(request(path, Some("wrong")))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), tonic::Code::Unauthenticated);
+
+        let req = interceptor
+            .intercept(request(path, Some("secret")))
+            .await
+            .unwrap();
+        assert_eq!(
+            req.extensions().get::<AuthenticatedClient>().unwrap().0,
+            "bot"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_bearer_token() {
+        let req = Request::builder()
+            .uri("/dcs.hook.v0.HookService/GetPaused")
+            .header(header::AUTHORIZATION, "Bearer secret")
+            .body(Body::empty())
+            .unwrap();
+        interceptor().intercept(req).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_public_services() {
+        let interceptor = interceptor();
+        for path in [
+            "/grpc.health.v1.Health/Check",
+            "/grpc.health.v1.Health/Watch",
+            "/grpc.reflection.v1.ServerReflection/ServerReflectionInfo",
+            "/grpc.reflection.v1alpha.ServerReflection/ServerReflectionInfo",
+        ] {
+            let req = interceptor.intercept(request(path, None)).await.unwrap();
+            assert!(req.extensions().get::<AuthenticatedClient>().is_none());
+        }
+
+        // only the exact service names are public
+        let err = interceptor
+            .intercept(request("/grpc.health.v1.HealthX/Check", None))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), tonic::Code::Unauthenticated);
+    }
+}
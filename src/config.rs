@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::net::SocketAddr;
 
 use serde::{Deserialize, Serialize};
@@ -22,6 +23,18 @@ pub struct Config {
     pub tts: Option<TtsConfig>,
     pub srs: Option<SrsConfig>,
     pub auth: Option<AuthConfig>,
+    pub grpc_web: Option<GrpcWebConfig>,
+    pub gateway: Option<GatewayConfig>,
+    pub websocket: Option<WebSocketConfig>,
+    #[serde(default)]
+    pub webhooks: Vec<WebhookConfig>,
+    #[serde(default)]
+    pub event_sinks: Vec<EventSinkConfig>,
+    pub streams: Option<StreamsConfig>,
+    pub gatekeeper: Option<GatekeeperConfig>,
+    pub bans: Option<BansConfig>,
+    pub players: Option<PlayersConfig>,
+    pub zones: Option<ZonesConfig>,
 }
 
 #[derive(Debug, Clone, Default, Deserialize, Serialize)]
@@ -104,6 +117,227 @@ pub struct ApiKey {
     pub token: String,
 }
 
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GrpcWebConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Origins allowed to access the server from a browser. Any origin is allowed if empty.
+    #[serde(default)]
+    pub cors_origins: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GatewayConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_gateway_port")]
+    pub port: u16,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebSocketConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_websocket_port")]
+    pub port: u16,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookConfig {
+    pub url: String,
+    /// The event types (e.g. `kill` or `missionEnd`) to post. All events are posted if empty.
+    #[serde(default)]
+    pub events: Vec<String>,
+    /// Only post events involving the given coalition.
+    pub coalition: Option<CoalitionFilter>,
+    /// Only post events involving one of the given players (by name or UCID).
+    #[serde(default)]
+    pub players: Vec<String>,
+    /// Additional headers to send with each request.
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+    /// The JSON payload template. The event is posted as is if not set.
+    pub template: Option<serde_json::Value>,
+}
+
+/// A coalition used to filter what is sent to webhooks or which units zone monitors watch.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CoalitionFilter {
+    Neutral,
+    Red,
+    Blue,
+}
+
+impl CoalitionFilter {
+    pub fn to_proto(self) -> i32 {
+        use stubs::common::v0::Coalition;
+        match self {
+            CoalitionFilter::Neutral => Coalition::Neutral.into(),
+            CoalitionFilter::Red => Coalition::Red.into(),
+            CoalitionFilter::Blue => Coalition::Blue.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StreamsConfig {
+    /// The number of messages buffered for each `StreamEvents` and `StreamUnits` subscriber.
+    #[serde(default = "default_event_buffer")]
+    pub event_buffer: usize,
+    /// Disconnect `StreamEvents` and `StreamUnits` subscribers once they dropped more than this
+    /// number of messages in total (never disconnect them if not set).
+    #[serde(default)]
+    pub max_dropped_events: Option<u64>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GatekeeperConfig {
+    /// Time in milliseconds to wait for the verdict of a gatekeeper (DCS is blocked while
+    /// waiting).
+    #[serde(default = "default_gatekeeper_timeout")]
+    pub timeout: u64,
+    /// The verdict used if a gatekeeper doesn't answer in time.
+    #[serde(default)]
+    pub default_verdict: GatekeeperVerdict,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GatekeeperVerdict {
+    #[default]
+    Allow,
+    Deny,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BansConfig {
+    /// The file the bans are persisted to (relative to DCS' write dir).
+    #[serde(default = "default_bans_path")]
+    pub path: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayersConfig {
+    /// The file the player sessions and sorties are persisted to (relative to DCS' write dir).
+    #[serde(default = "default_players_path")]
+    pub path: String,
+    /// The number of sessions kept; the oldest ended sessions are removed beyond that.
+    #[serde(default = "default_players_max_sessions")]
+    pub max_sessions: usize,
+    /// The number of sorties kept; the oldest ended sorties are removed beyond that.
+    #[serde(default = "default_players_max_sorties")]
+    pub max_sorties: usize,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ZonesConfig {
+    /// The interval in seconds in which the units are checked against the monitored zones.
+    #[serde(default = "default_zones_poll_rate")]
+    pub poll_rate: u64,
+    /// The trigger zones to emit `ZoneEnter` and `ZoneLeave` events for.
+    #[serde(default)]
+    pub monitors: Vec<ZoneMonitorConfig>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ZoneMonitorConfig {
+    /// The name of the trigger zone (as defined in the mission editor).
+    pub zone: String,
+    /// Only monitor units of the given coalition.
+    pub coalition: Option<CoalitionFilter>,
+    /// Only monitor units of the given categories. All units are monitored if empty.
+    #[serde(default)]
+    pub categories: Vec<ZoneUnitCategory>,
+    /// Only monitor units of the given types (e.g. `F-16C_50`). All units are monitored if empty.
+    #[serde(default)]
+    pub unit_types: Vec<String>,
+    /// Only monitor units controlled by players.
+    #[serde(default)]
+    pub players_only: bool,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ZoneUnitCategory {
+    Airplane,
+    Helicopter,
+    Ground,
+    Ship,
+}
+
+impl ZoneUnitCategory {
+    pub fn to_proto(self) -> stubs::common::v0::GroupCategory {
+        use stubs::common::v0::GroupCategory;
+        match self {
+            ZoneUnitCategory::Airplane => GroupCategory::Airplane,
+            ZoneUnitCategory::Helicopter => GroupCategory::Helicopter,
+            ZoneUnitCategory::Ground => GroupCategory::Ground,
+            ZoneUnitCategory::Ship => GroupCategory::Ship,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum EventSinkConfig {
+    File(FileSinkConfig),
+    Tcp(TcpSinkConfig),
+    Udp(UdpSinkConfig),
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileSinkConfig {
+    /// The path of the file (relative to the DCS write dir).
+    #[serde(default = "default_file_sink_path")]
+    pub path: String,
+    /// The size in bytes at which the file is rotated.
+    #[serde(default = "default_file_sink_max_size")]
+    pub max_size: u64,
+    /// The number of rotated files to keep.
+    #[serde(default = "default_file_sink_max_files")]
+    pub max_files: u32,
+    #[serde(default = "default_event_sink_buffer")]
+    pub buffer: usize,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TcpSinkConfig {
+    pub addr: SocketAddr,
+    #[serde(default = "default_event_sink_buffer")]
+    pub buffer: usize,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UdpSinkConfig {
+    pub addr: SocketAddr,
+    #[serde(default)]
+    pub format: UdpSinkFormat,
+    #[serde(default = "default_event_sink_buffer")]
+    pub buffer: usize,
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UdpSinkFormat {
+    #[default]
+    Json,
+    Syslog,
+}
+
 fn default_host() -> String {
     String::from("127.0.0.1")
 }
@@ -112,6 +346,121 @@ fn default_port() -> u16 {
     50051
 }
 
+fn default_gateway_port() -> u16 {
+    50052
+}
+
+fn default_websocket_port() -> u16 {
+    50053
+}
+
+fn default_file_sink_path() -> String {
+    String::from("Logs/gRPC-events.ndjson")
+}
+
+fn default_file_sink_max_size() -> u64 {
+    10 * 1024 * 1024
+}
+
+fn default_file_sink_max_files() -> u32 {
+    5
+}
+
+fn default_event_sink_buffer() -> usize {
+    1024
+}
+
+fn default_event_buffer() -> usize {
+    1024
+}
+
+fn default_gatekeeper_timeout() -> u64 {
+    500
+}
+
+fn default_bans_path() -> String {
+    String::from("Config/dcs-grpc-bans.json")
+}
+
+fn default_players_path() -> String {
+    String::from("Config/dcs-grpc-players.json")
+}
+
+fn default_players_max_sessions() -> usize {
+    10_000
+}
+
+fn default_players_max_sorties() -> usize {
+    50_000
+}
+
+fn default_zones_poll_rate() -> u64 {
+    5
+}
+
+impl Default for GatewayConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: default_gateway_port(),
+        }
+    }
+}
+
+impl Default for GatekeeperConfig {
+    fn default() -> Self {
+        Self {
+            timeout: default_gatekeeper_timeout(),
+            default_verdict: GatekeeperVerdict::default(),
+        }
+    }
+}
+
+impl Default for BansConfig {
+    fn default() -> Self {
+        Self {
+            path: default_bans_path(),
+        }
+    }
+}
+
+impl Default for PlayersConfig {
+    fn default() -> Self {
+        Self {
+            path: default_players_path(),
+            max_sessions: default_players_max_sessions(),
+            max_sorties: default_players_max_sorties(),
+        }
+    }
+}
+
+impl Default for ZonesConfig {
+    fn default() -> Self {
+        Self {
+            poll_rate: default_zones_poll_rate(),
+            monitors: Vec::new(),
+        }
+    }
+}
+
+impl Default for StreamsConfig {
+    fn default() -> Self {
+        Self {
+            event_buffer: default_event_buffer(),
+            max_dropped_events: None,
+        }
+    }
+}
+
+impl Default for WebSocketConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: default_websocket_port(),
+        }
+    }
+}
+
 impl mlua::FromLua for Config {
     fn from_lua(lua_value: mlua::Value, lua: &mlua::Lua) -> mlua::Result<Self> {
         use mlua::LuaSerdeExt;
@@ -161,3 +510,30 @@ impl std::fmt::Debug for GCloudConfig {
             .finish()
     }
 }
+
+impl std::fmt::Debug for WebhookConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let WebhookConfig {
+            url: _,
+            events,
+            coalition,
+            players,
+            headers,
+            template,
+        } = self;
+        f.debug_struct("WebhookConfig")
+            .field("url", &"<REDACTED>")
+            .field("events", events)
+            .field("coalition", coalition)
+            .field("players", players)
+            .field(
+                "headers",
+                &headers
+                    .keys()
+                    .map(|k| (k, "<REDACTED>"))
+                    .collect::<HashMap<_, _>>(),
+            )
+            .field("template", template)
+            .finish()
+    }
+}
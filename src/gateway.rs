@@ -0,0 +1,686 @@
+use std::convert::Infallible;
+use std::future::Future;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use axum::Router;
+use axum::body::Bytes;
+use axum::extract::rejection::QueryRejection;
+use axum::extract::{Extension, Path, Query, Request as HttpRequest, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::{IntoResponse, Response as HttpResponse};
+use axum::routing::{get, post};
+use futures_util::{Stream, StreamExt};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use stubs::atmosphere::v0::atmosphere_service_server::AtmosphereService;
+use stubs::ban::v0::ban_service_server::BanService;
+use stubs::coalition::v0::coalition_service_server::CoalitionService;
+use stubs::controller::v0::controller_service_server::ControllerService;
+use stubs::custom::v0::custom_service_server::CustomService;
+use stubs::group::v0::group_service_server::GroupService;
+use stubs::hook::v0::hook_service_server::HookService;
+use stubs::land::v0::land_service_server::LandService;
+use stubs::metadata::v0::metadata_service_server::MetadataService;
+use stubs::mission::v0::mission_service_server::MissionService;
+use stubs::mission::v0::{StreamEventsRequest, StreamUnitsRequest};
+use stubs::net::v0::net_service_server::NetService;
+use stubs::player::v0::player_service_server::PlayerService;
+use stubs::srs::v0::srs_service_server::SrsService;
+use stubs::timer::v0::timer_service_server::TimerService;
+use stubs::trigger::v0::trigger_service_server::TriggerService;
+use stubs::unit::v0::unit_service_server::UnitService;
+use stubs::world::v0::world_service_server::WorldService;
+use stubs::zone::v0::zone_service_server::ZoneService;
+use tokio::net::TcpListener;
+use tonic::{Code, Request, Response, Status};
+
+use crate::authentication::{AuthInterceptor, AuthenticatedClient};
+use crate::rpc::{Bans, HookRpc, MissionRpc, Players, Srs};
+use crate::shutdown::ShutdownHandle;
+
+/// An optional HTTP/JSON gateway for clients that can't easily speak gRPC. Each unary RPC is
+/// available as `POST /v0/{service}/{method}` (e.g. `POST /v0/mission/GetScenarioCurrentTime`),
+/// with the request and response messages encoded as JSON using the serde representation of the
+/// `stubs` types. `StreamEvents` and `StreamUnits` are available as Server-Sent Events, either via
+/// `POST` with a JSON body or via `GET` with the request fields as query parameters (for browser
+/// `EventSource`s, which can only send `GET` requests).
+pub struct Gateway {
+    pub mission: MissionRpc,
+    pub hook: HookRpc,
+    pub srs: Srs,
+    pub bans: Bans,
+    pub players: Players,
+    pub auth: AuthInterceptor,
+}
+
+pub async fn run(
+    gateway: Gateway,
+    addr: SocketAddr,
+    shutdown_signal: ShutdownHandle,
+) -> Result<(), std::io::Error> {
+    log::info!("Staring HTTP/JSON gateway (on {}) ...", addr);
+
+    let app = router(Arc::new(gateway));
+    let listener = TcpListener::bind(addr).await?;
+    axum::serve(listener, app)
+        .with_graceful_shutdown(shutdown_signal.signal())
+        .await?;
+
+    log::info!("HTTP/JSON gateway stopped ...");
+
+    Ok(())
+}
+
+fn router(gateway: Arc<Gateway>) -> Router {
+    Router::new()
+        .route(
+            "/v0/mission/StreamEvents",
+            get(stream_events_query).post(stream_events),
+        )
+        .route(
+            "/v0/mission/StreamUnits",
+            get(stream_units_query).post(stream_units),
+        )
+        .route("/v0/{service}/{method}", post(unary))
+        .layer(middleware::from_fn_with_state(
+            gateway.clone(),
+            authenticate,
+        ))
+        .with_state(gateway)
+}
+
+/// Same authentication as the [AuthInterceptor] applies to gRPC requests.
+async fn authenticate(
+    State(gateway): State<Arc<Gateway>>,
+    headers: HeaderMap,
+    mut req: HttpRequest,
+    next: Next,
+) -> HttpResponse {
+    if gateway.auth.auth_config.enabled {
+        match gateway.auth.authenticate(&headers) {
+            Some(client) => {
+                log::debug!("Authenticated client: {}", client);
+                let client = AuthenticatedClient(client.to_string());
+                req.extensions_mut().insert(client);
+            }
+            None => return error_response(Status::unauthenticated("Unauthenticated")),
+        }
+    }
+
+    next.run(req).await
+}
+
+async fn unary(
+    State(gateway): State<Arc<Gateway>>,
+    Path((service, method)): Path<(String, String)>,
+    client: Option<Extension<AuthenticatedClient>>,
+    body: Bytes,
+) -> HttpResponse {
+    let client = client.map(|Extension(client)| client);
+    match dispatch(&gateway, &service, &method, &body, client).await {
+        Ok(res) => axum::Json(res).into_response(),
+        Err(err) => error_response(err),
+    }
+}
+
+async fn stream_events(State(gateway): State<Arc<Gateway>>, body: Bytes) -> HttpResponse {
+    stream(deserialize(&body), |req| {
+        MissionService::stream_events(&gateway.mission, req)
+    })
+    .await
+}
+
+async fn stream_events_query(
+    State(gateway): State<Arc<Gateway>>,
+    query: Result<Query<StreamEventsRequest>, QueryRejection>,
+) -> HttpResponse {
+    stream(from_query(query), |req| {
+        MissionService::stream_events(&gateway.mission, req)
+    })
+    .await
+}
+
+async fn stream_units(State(gateway): State<Arc<Gateway>>, body: Bytes) -> HttpResponse {
+    stream(deserialize(&body), |req| {
+        MissionService::stream_units(&gateway.mission, req)
+    })
+    .await
+}
+
+async fn stream_units_query(
+    State(gateway): State<Arc<Gateway>>,
+    query: Result<Query<StreamUnitsRequest>, QueryRejection>,
+) -> HttpResponse {
+    stream(from_query(query), |req| {
+        MissionService::stream_units(&gateway.mission, req)
+    })
+    .await
+}
+
+/// Calls the given server-streaming RPC and forwards each streamed message as Server-Sent Event.
+/// A failure of the stream is sent as a final `error` event.
+async fn stream<I, O, S, F, Fut>(req: Result<I, String>, f: F) -> HttpResponse
+where
+    O: Serialize + Send + 'static,
+    S: Stream<Item = Result<O, Status>> + Send + 'static,
+    F: FnOnce(Request<I>) -> Fut,
+    Fut: Future<Output = Result<Response<S>, Status>>,
+{
+    let req = match req {
+        Ok(req) => req,
+        Err(err) => return error_response(Status::invalid_argument(err)),
+    };
+    let stream = match f(Request::new(req)).await {
+        Ok(res) => res.into_inner(),
+        Err(err) => return error_response(err),
+    };
+
+    let events = stream
+        .scan(false, |failed, item| {
+            // end the stream after the first error
+            if *failed {
+                return std::future::ready(None);
+            }
+            let event = match item {
+                Ok(item) => Event::default().json_data(item).unwrap_or_else(|err| {
+                    *failed = true;
+                    Event::default().event("error").data(err.to_string())
+                }),
+                Err(err) => {
+                    *failed = true;
+                    Event::default().event("error").data(err.message())
+                }
+            };
+            std::future::ready(Some(Ok::<_, Infallible>(event)))
+        })
+        .boxed();
+
+    Sse::new(events)
+        .keep_alive(KeepAlive::default())
+        .into_response()
+}
+
+/// Calls the given unary RPC with the JSON `body` deserialized as its request.
+async fn call<I, O, F, Fut>(
+    body: &[u8],
+    client: Option<AuthenticatedClient>,
+    f: F,
+) -> Result<serde_json::Value, Status>
+where
+    I: DeserializeOwned,
+    O: Serialize,
+    F: FnOnce(Request<I>) -> Fut,
+    Fut: Future<Output = Result<Response<O>, Status>>,
+{
+    let mut req = Request::new(deserialize(body).map_err(Status::invalid_argument)?);
+    if let Some(client) = client {
+        req.extensions_mut().insert(client);
+    }
+    let res = f(req).await?;
+    serde_json::to_value(res.into_inner())
+        .map_err(|err| Status::internal(format!("failed to serialize response: {err}")))
+}
+
+fn deserialize<I: DeserializeOwned>(body: &[u8]) -> Result<I, String> {
+    // allow requests without any parameters to omit the body entirely
+    let body = if body.iter().all(u8::is_ascii_whitespace) {
+        b"{}".as_slice()
+    } else {
+        body
+    };
+    serde_json::from_slice(body).map_err(|err| format!("invalid request body: {err}"))
+}
+
+fn from_query<I>(query: Result<Query<I>, QueryRejection>) -> Result<I, String> {
+    query
+        .map(|Query(req)| req)
+        .map_err(|err| format!("invalid query parameters: {err}"))
+}
+
+fn error_response(status: Status) -> HttpResponse {
+    let code = match status.code() {
+        Code::InvalidArgument | Code::OutOfRange | Code::FailedPrecondition => {
+            StatusCode::BAD_REQUEST
+        }
+        Code::Unauthenticated => StatusCode::UNAUTHORIZED,
+        Code::PermissionDenied => StatusCode::FORBIDDEN,
+        Code::NotFound => StatusCode::NOT_FOUND,
+        Code::AlreadyExists | Code::Aborted => StatusCode::CONFLICT,
+        Code::ResourceExhausted => StatusCode::TOO_MANY_REQUESTS,
+        Code::Unimplemented => StatusCode::NOT_IMPLEMENTED,
+        Code::Unavailable => StatusCode::SERVICE_UNAVAILABLE,
+        Code::DeadlineExceeded => StatusCode::GATEWAY_TIMEOUT,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+
+    (
+        code,
+        axum::Json(serde_json::json!({
+            "code": format!("{:?}", status.code()),
+            "message": status.message(),
+        })),
+    )
+        .into_response()
+}
+
+/// Generates the dispatch of a `{service}/{method}` pair to the corresponding unary RPC.
+macro_rules! dispatch {
+    ($($service:literal => $rpc:ident: $trait:ident { $($method:ident => $fn:ident,)* },)*) => {
+        async fn dispatch(
+            gateway: &Gateway,
+            service: &str,
+            method: &str,
+            body: &[u8],
+            client: Option<AuthenticatedClient>,
+        ) -> Result<serde_json::Value, Status> {
+            match (service, method) {
+                $($(
+                    ($service, stringify!($method)) => {
+                        call(body, client, |req| $trait::$fn(&gateway.$rpc, req)).await
+                    }
+                )*)*
+                _ => Err(Status::not_found(format!("unknown method `{service}/{method}`"))),
+            }
+        }
+
+        /// All `{service}/{method}` pairs handled by [dispatch].
+        #[cfg(test)]
+        const METHODS: &[(&str, &str)] = &[$($(($service, stringify!($method)),)*)*];
+    };
+}
+
+dispatch! {
+    "atmosphere" => mission: AtmosphereService {
+        GetWind => get_wind,
+        GetWindWithTurbulence => get_wind_with_turbulence,
+        GetTemperatureAndPressure => get_temperature_and_pressure,
+    },
+    "ban" => bans: BanService {
+        AddBan => add_ban,
+        ListBans => list_bans,
+        SearchBans => search_bans,
+        ExpireBan => expire_ban,
+    },
+    "coalition" => mission: CoalitionService {
+        AddGroup => add_group,
+        GetStaticObjects => get_static_objects,
+        AddStaticObject => add_static_object,
+        AddLinkedStatic => add_linked_static,
+        GetGroups => get_groups,
+        GetBullseye => get_bullseye,
+        GetPlayerUnits => get_player_units,
+    },
+    "controller" => mission: ControllerService {
+        SetAlarmState => set_alarm_state,
+        GetDetectedTargets => get_detected_targets,
+        SetTask => set_task,
+        PushTask => push_task,
+        PopTask => pop_task,
+        ResetTask => reset_task,
+        HasTask => has_task,
+        SetOption => set_option,
+        SetCommand => set_command,
+        SetOnOff => set_on_off,
+    },
+    "custom" => mission: CustomService {
+        RequestMissionAssignment => request_mission_assignment,
+        JoinMission => join_mission,
+        AbortMission => abort_mission,
+        GetMissionStatus => get_mission_status,
+        Eval => eval,
+        GetMagneticDeclination => get_magnetic_declination,
+        Call => call,
+        ListMethods => list_methods,
+    },
+    "group" => mission: GroupService {
+        GetUnits => get_units,
+        Activate => activate,
+        Destroy => destroy,
+        Get => get,
+        GetSize => get_size,
+        GetInitialSize => get_initial_size,
+        GetCategory => get_category,
+        GetController => get_controller,
+        StopMoving => stop_moving,
+        ContinueMoving => continue_moving,
+        SetAiOnOff => set_ai_on_off,
+        Deactivate => deactivate,
+    },
+    "hook" => hook: HookService {
+        GetMissionName => get_mission_name,
+        GetMissionFilename => get_mission_filename,
+        GetMissionDescription => get_mission_description,
+        GetPaused => get_paused,
+        SetPaused => set_paused,
+        StopMission => stop_mission,
+        ReloadCurrentMission => reload_current_mission,
+        LoadNextMission => load_next_mission,
+        LoadMission => load_mission,
+        Eval => eval,
+        ExitProcess => exit_process,
+        IsMultiplayer => is_multiplayer,
+        IsServer => is_server,
+        BanPlayer => ban_player,
+        UnbanPlayer => unban_player,
+        GetBannedPlayers => get_banned_players,
+        GetUnitType => get_unit_type,
+        GetRealTime => get_real_time,
+        GetBallisticsCount => get_ballistics_count,
+        GetModelTime => get_model_time,
+        GetPlayerStat => get_player_stat,
+        GetMyPlayerId => get_my_player_id,
+        GetServerId => get_server_id,
+        SetSlot => set_slot,
+        GetAvailableSlots => get_available_slots,
+        GetAvailableCoalitions => get_available_coalitions,
+        GetCurrentMission => get_current_mission,
+        GetMissionOptions => get_mission_options,
+    },
+    "land" => mission: LandService {
+        GetHeight => get_height,
+        BatchGetHeight => batch_get_height,
+        GetSurfaceHeightWithSeabed => get_surface_height_with_seabed,
+        BatchGetSurfaceHeightWithSeabed => batch_get_surface_height_with_seabed,
+        GetSurfaceType => get_surface_type,
+        BatchGetSurfaceType => batch_get_surface_type,
+        IsVisible => is_visible,
+        BatchIsVisible => batch_is_visible,
+        GetIntersectionPoint => get_intersection_point,
+        BatchGetIntersectionPoint => batch_get_intersection_point,
+        GetProfile => get_profile,
+        GetClosestPointOnRoads => get_closest_point_on_roads,
+        FindPathOnRoads => find_path_on_roads,
+        PathToGroundRoute => path_to_ground_route,
+    },
+    "metadata" => mission: MetadataService {
+        GetHealth => get_health,
+        GetVersion => get_version,
+    },
+    "mission" => mission: MissionService {
+        GetScenarioStartTime => get_scenario_start_time,
+        GetScenarioCurrentTime => get_scenario_current_time,
+        AddMissionCommand => add_mission_command,
+        AddMissionCommandSubMenu => add_mission_command_sub_menu,
+        RemoveMissionCommandItem => remove_mission_command_item,
+        AddCoalitionCommand => add_coalition_command,
+        AddCoalitionCommandSubMenu => add_coalition_command_sub_menu,
+        RemoveCoalitionCommandItem => remove_coalition_command_item,
+        AddGroupCommand => add_group_command,
+        AddGroupCommandSubMenu => add_group_command_sub_menu,
+        RemoveGroupCommandItem => remove_group_command_item,
+        AddMissionOtherCommand => add_mission_other_command,
+        RemoveMissionOtherCommand => remove_mission_other_command,
+        AddCoalitionOtherCommand => add_coalition_other_command,
+        RemoveCoalitionOtherCommand => remove_coalition_other_command,
+        AddGroupOtherCommand => add_group_other_command,
+        RemoveGroupOtherCommand => remove_group_other_command,
+        ListCommands => list_commands,
+        GetSessionId => get_session_id,
+    },
+    "net" => mission: NetService {
+        SendChatTo => send_chat_to,
+        SendChat => send_chat,
+        GetPlayers => get_players,
+        KickPlayer => kick_player,
+        ForcePlayerSlot => force_player_slot,
+    },
+    "player" => players: PlayerService {
+        ListSessions => list_sessions,
+        ListSorties => list_sorties,
+        GetPlayerStats => get_player_stats,
+    },
+    "srs" => srs: SrsService {
+        Transmit => transmit,
+        GetClients => get_clients,
+    },
+    "timer" => mission: TimerService {
+        GetTime => get_time,
+        GetAbsoluteTime => get_absolute_time,
+        GetTimeZero => get_time_zero,
+    },
+    "trigger" => mission: TriggerService {
+        OutText => out_text,
+        OutTextForCoalition => out_text_for_coalition,
+        OutTextForGroup => out_text_for_group,
+        OutTextForUnit => out_text_for_unit,
+        OutTextForCountry => out_text_for_country,
+        OutSound => out_sound,
+        OutSoundForCoalition => out_sound_for_coalition,
+        OutSoundForCountry => out_sound_for_country,
+        OutSoundForGroup => out_sound_for_group,
+        RadioTransmission => radio_transmission,
+        StopRadioTransmission => stop_radio_transmission,
+        GetUserFlag => get_user_flag,
+        SetUserFlag => set_user_flag,
+        MarkToAll => mark_to_all,
+        MarkToCoalition => mark_to_coalition,
+        MarkToGroup => mark_to_group,
+        MarkupToAll => markup_to_all,
+        MarkupToCoalition => markup_to_coalition,
+        RemoveMark => remove_mark,
+        LineToAll => line_to_all,
+        CircleToAll => circle_to_all,
+        RectToAll => rect_to_all,
+        QuadToAll => quad_to_all,
+        TextToAll => text_to_all,
+        ArrowToAll => arrow_to_all,
+        UpdateDrawing => update_drawing,
+        ListDrawings => list_drawings,
+        ClearDrawings => clear_drawings,
+        Explosion => explosion,
+        Smoke => smoke,
+        IlluminationBomb => illumination_bomb,
+        SignalFlare => signal_flare,
+    },
+    "unit" => mission: UnitService {
+        GetRadar => get_radar,
+        GetPosition => get_position,
+        GetPlayerName => get_player_name,
+        GetDescriptor => get_descriptor,
+        SetEmission => set_emission,
+        Get => get,
+        GetTransform => get_transform,
+        Destroy => destroy,
+        GetDrawArgumentValue => get_draw_argument_value,
+    },
+    "world" => mission: WorldService {
+        GetAirbases => get_airbases,
+        GetMarkPanels => get_mark_panels,
+        GetTheatre => get_theatre,
+        SearchObjects => search_objects,
+    },
+    "zone" => mission: ZoneService {
+        GetZones => get_zones,
+        GetZone => get_zone,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::body::Body;
+    use axum::http::Method;
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::config::{ApiKey, AuthConfig, PlayersConfig};
+    use crate::health::Health;
+    use crate::shutdown::Shutdown;
+    use crate::stats::Stats;
+
+    fn gateway(auth_config: AuthConfig) -> (Router, Shutdown) {
+        let shutdown = Shutdown::new();
+        let stats = Stats::new(shutdown.handle());
+        let mission = MissionRpc::new(
+            Default::default(),
+            stats.clone(),
+            Health::new(stats.clone()),
+            shutdown.handle(),
+        );
+        let dir = std::env::temp_dir().join(format!("dcs-grpc-gateway-{}", std::process::id()));
+        let gateway = Gateway {
+            hook: HookRpc::new(Default::default(), stats, shutdown.handle()),
+            srs: Srs::new(
+                Default::default(),
+                Default::default(),
+                mission.clone(),
+                Default::default(),
+                shutdown.handle(),
+            ),
+            bans: Bans::load(dir.join("bans.json")),
+            players: Players::load(dir.join("players.json"), &PlayersConfig::default()),
+            mission,
+            auth: AuthInterceptor { auth_config },
+        };
+        (router(Arc::new(gateway)), shutdown)
+    }
+
+    async fn request(
+        router: &Router,
+        method: Method,
+        uri: &str,
+        body: &'static str,
+    ) -> (StatusCode, HttpResponse) {
+        let req = axum::http::Request::builder()
+            .method(method)
+            .uri(uri)
+            .body(Body::from(body))
+            .unwrap();
+        let res = router.clone().oneshot(req).await.unwrap();
+        (res.status(), res)
+    }
+
+    async fn error(res: HttpResponse) -> (String, String) {
+        let body = axum::body::to_bytes(res.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        (
+            body["code"].as_str().unwrap().to_string(),
+            body["message"].as_str().unwrap().to_string(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_dispatch() {
+        let (router, _shutdown) = gateway(AuthConfig::default());
+
+        // an invalid body is rejected before the RPC is called, which proves that each method is
+        // routed to an RPC without requiring a running mission
+        for (service, method) in METHODS {
+            let uri = format!("/v0/{service}/{method}");
+            let (status, res) = request(&router, Method::POST, &uri, "[").await;
+            assert_eq!(status, StatusCode::BAD_REQUEST, "{uri}");
+            let (code, message) = error(res).await;
+            assert_eq!(code, "InvalidArgument", "{uri}");
+            assert!(
+                message.starts_with("invalid request body"),
+                "{uri}: {message}"
+            );
+        }
+
+        let (status, res) = request(&router, Method::POST, "/v0/mission/Unknown", "{}").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(
+            error(res).await,
+            (
+                "NotFound".to_string(),
+                "unknown method `mission/Unknown`".to_string()
+            )
+        );
+
+        // only `POST` is routed to unary RPCs
+        let (status, _) = request(&router, Method::GET, "/v0/mission/GetSessionId", "").await;
+        assert_eq!(status, StatusCode::METHOD_NOT_ALLOWED);
+    }
+
+    #[tokio::test]
+    async fn test_authentication() {
+        let (router, _shutdown) = gateway(AuthConfig {
+            enabled: true,
+            tokens: vec![ApiKey {
+                client: "bot".to_string(),
+                token: "secret".to_string(),
+            }],
+        });
+
+        let (status, res) = request(&router, Method::POST, "/v0/mission/GetSessionId", "").await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(error(res).await.0, "Unauthenticated");
+
+        let req = axum::http::Request::builder()
+            .method(Method::POST)
+            .uri("/v0/mission/AddMissionCommand")
+            .header("X-API-Key", "secret")
+            .body(Body::from("["))
+            .unwrap();
+        let res = router.oneshot(req).await.unwrap();
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_stream_query() {
+        let (router, _shutdown) = gateway(AuthConfig::default());
+
+        let (status, res) = request(
+            &router,
+            Method::GET,
+            "/v0/mission/StreamUnits?pollRate=2&maxBackoff=10&category=1",
+            "",
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            res.headers()[axum::http::header::CONTENT_TYPE],
+            "text/event-stream"
+        );
+
+        let (status, res) = request(
+            &router,
+            Method::GET,
+            "/v0/mission/StreamUnits?pollRate=fast",
+            "",
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let (code, message) = error(res).await;
+        assert_eq!(code, "InvalidArgument");
+        assert!(message.starts_with("invalid query parameters"), "{message}");
+
+        let (status, _) = request(&router, Method::GET, "/v0/mission/StreamEvents", "").await;
+        assert_eq!(status, StatusCode::OK);
+
+        let (status, res) = request(&router, Method::POST, "/v0/mission/StreamUnits", "[").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(error(res).await.1.starts_with("invalid request body"));
+    }
+
+    #[tokio::test]
+    async fn test_error_response() {
+        for (code, status) in [
+            (Code::InvalidArgument, StatusCode::BAD_REQUEST),
+            (Code::OutOfRange, StatusCode::BAD_REQUEST),
+            (Code::FailedPrecondition, StatusCode::BAD_REQUEST),
+            (Code::Unauthenticated, StatusCode::UNAUTHORIZED),
+            (Code::PermissionDenied, StatusCode::FORBIDDEN),
+            (Code::NotFound, StatusCode::NOT_FOUND),
+            (Code::AlreadyExists, StatusCode::CONFLICT),
+            (Code::Aborted, StatusCode::CONFLICT),
+            (Code::ResourceExhausted, StatusCode::TOO_MANY_REQUESTS),
+            (Code::Unimplemented, StatusCode::NOT_IMPLEMENTED),
+            (Code::Unavailable, StatusCode::SERVICE_UNAVAILABLE),
+            (Code::DeadlineExceeded, StatusCode::GATEWAY_TIMEOUT),
+            (Code::Internal, StatusCode::INTERNAL_SERVER_ERROR),
+            (Code::Unknown, StatusCode::INTERNAL_SERVER_ERROR),
+        ] {
+            let res = error_response(Status::new(code, "failed"));
+            assert_eq!(res.status(), status, "{code:?}");
+            assert_eq!(
+                error(res).await,
+                (format!("{code:?}"), "failed".to_string())
+            );
+        }
+    }
+}
@@ -0,0 +1,460 @@
+use std::future::Future;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use futures_util::future::BoxFuture;
+use once_cell::sync::Lazy;
+use stubs::atmosphere::v0::atmosphere_service_server::AtmosphereServiceServer;
+use stubs::coalition::v0::coalition_service_server::CoalitionServiceServer;
+use stubs::controller::v0::controller_service_server::ControllerServiceServer;
+use stubs::custom::v0::custom_service_server::CustomServiceServer;
+use stubs::group::v0::group_service_server::GroupServiceServer;
+use stubs::hook::v0::hook_service_server::HookServiceServer;
+use stubs::hook::v0::{GetPausedRequest, GetPausedResponse};
+use stubs::land::v0::land_service_server::LandServiceServer;
+use stubs::metadata::v0::{GetHealthResponse, HealthStatus};
+use stubs::mission::v0::mission_service_server::MissionServiceServer;
+use stubs::net::v0::net_service_server::NetServiceServer;
+use stubs::srs::v0::srs_service_server::SrsServiceServer;
+use stubs::timer::v0::timer_service_server::TimerServiceServer;
+use stubs::timer::v0::{GetTimeRequest, GetTimeResponse};
+use stubs::trigger::v0::trigger_service_server::TriggerServiceServer;
+use stubs::unit::v0::unit_service_server::UnitServiceServer;
+use stubs::world::v0::world_service_server::WorldServiceServer;
+use stubs::zone::v0::zone_service_server::ZoneServiceServer;
+use tokio::time::{MissedTickBehavior, interval, timeout};
+use tonic::server::NamedService;
+use tonic::{Request, Status};
+use tonic_health::ServingStatus;
+use tonic_health::server::HealthReporter;
+
+use crate::rpc::{HookRpc, MissionRpc, Srs};
+use crate::stats::Stats;
+
+/// How often both Lua environments are probed.
+const PROBE_INTERVAL: Duration = Duration::from_secs(5);
+/// How long to wait for a probe to be answered before considering the environment unresponsive.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// For how long no simulation frame may be received before the server is considered unhealthy.
+const FRAME_THRESHOLD: Duration = Duration::from_secs(10);
+/// For how long no request may be processed (while requests are queued) before the server is
+/// considered unhealthy.
+const REQUEST_THRESHOLD: Duration = Duration::from_secs(10);
+
+/// Reference point for all timestamps tracked as atomics below.
+static START: Lazy<Instant> = Lazy::new(Instant::now);
+/// Time of the last simulation frame in milliseconds since [START] (`0` if there was none yet).
+static LAST_FRAME: AtomicU64 = AtomicU64::new(0);
+
+/// All services that are executed inside of the mission scripting environment.
+const MISSION_SERVICES: &[&str] = &[
+    <AtmosphereServiceServer<MissionRpc> as NamedService>::NAME,
+    <CoalitionServiceServer<MissionRpc> as NamedService>::NAME,
+    <ControllerServiceServer<MissionRpc> as NamedService>::NAME,
+    <CustomServiceServer<MissionRpc> as NamedService>::NAME,
+    <GroupServiceServer<MissionRpc> as NamedService>::NAME,
+    <LandServiceServer<MissionRpc> as NamedService>::NAME,
+    <MissionServiceServer<MissionRpc> as NamedService>::NAME,
+    <NetServiceServer<MissionRpc> as NamedService>::NAME,
+    <SrsServiceServer<Srs> as NamedService>::NAME,
+    <TimerServiceServer<MissionRpc> as NamedService>::NAME,
+    <TriggerServiceServer<MissionRpc> as NamedService>::NAME,
+    <UnitServiceServer<MissionRpc> as NamedService>::NAME,
+    <WorldServiceServer<MissionRpc> as NamedService>::NAME,
+    <ZoneServiceServer<MissionRpc> as NamedService>::NAME,
+];
+
+/// All services that are executed inside of the hook environment.
+const HOOK_SERVICES: &[&str] = &[<HookServiceServer<HookRpc> as NamedService>::NAME];
+
+/// Called on each simulation frame, so keep it as cheap as possible.
+pub fn track_frame() {
+    LAST_FRAME.store(now(), Ordering::Relaxed);
+}
+
+/// Collects the signals used to determine whether the server is able to serve requests.
+#[derive(Clone)]
+pub struct Health(Arc<Inner>);
+
+struct Inner {
+    stats: Stats,
+    /// Time of the last processed request in milliseconds since [START] (`0` if there was none
+    /// yet).
+    last_request: AtomicU64,
+    /// Whether the mission environment answered the last probe.
+    mission_loaded: AtomicBool,
+    /// Whether the hook environment reported the mission as paused on the last probe.
+    paused: AtomicBool,
+    /// The number of currently open connections to SRS.
+    srs_connections: AtomicUsize,
+}
+
+impl Health {
+    pub fn new(stats: Stats) -> Self {
+        Health(Arc::new(Inner {
+            stats,
+            last_request: AtomicU64::new(0),
+            mission_loaded: AtomicBool::new(false),
+            paused: AtomicBool::new(false),
+            srs_connections: AtomicUsize::new(0),
+        }))
+    }
+
+    pub fn track_request(&self) {
+        self.0.last_request.store(now(), Ordering::Relaxed);
+    }
+
+    pub fn track_srs_connection(&self) -> TrackSrsConnectionGuard {
+        self.0.srs_connections.fetch_add(1, Ordering::Relaxed);
+        TrackSrsConnectionGuard {
+            health: self.0.clone(),
+        }
+    }
+
+    /// Create a report of all tracked signals, including a verdict whether the server is able
+    /// to serve requests and the reasons if not.
+    pub fn report(&self) -> GetHealthResponse {
+        Signals {
+            since_last_frame: since(LAST_FRAME.load(Ordering::Relaxed)),
+            since_last_request: since(self.0.last_request.load(Ordering::Relaxed)),
+            queue_size: self.0.stats.queue_size(),
+            srs_connected: self.0.srs_connections.load(Ordering::Relaxed) > 0,
+            mission_loaded: self.0.mission_loaded.load(Ordering::Relaxed),
+            paused: self.0.paused.load(Ordering::Relaxed),
+        }
+        .report()
+    }
+}
+
+/// A snapshot of the tracked signals.
+struct Signals {
+    since_last_frame: Option<Duration>,
+    since_last_request: Option<Duration>,
+    queue_size: u32,
+    srs_connected: bool,
+    mission_loaded: bool,
+    paused: bool,
+}
+
+impl Signals {
+    fn report(self) -> GetHealthResponse {
+        let mut reasons = Vec::new();
+        if !self.mission_loaded {
+            reasons.push("mission environment does not answer requests".to_string());
+        }
+        if self.paused {
+            reasons.push("mission is paused".to_string());
+        }
+        match self.since_last_frame {
+            None => reasons.push("no simulation frame received yet".to_string()),
+            Some(elapsed) if elapsed > FRAME_THRESHOLD => reasons.push(format!(
+                "no simulation frame received for {:.1}s",
+                elapsed.as_secs_f64()
+            )),
+            Some(_) => {}
+        }
+        if self.queue_size > 0 {
+            let queue_size = self.queue_size;
+            match self.since_last_request {
+                None => reasons.push(format!(
+                    "{queue_size} requests queued, but none processed yet"
+                )),
+                Some(elapsed) if elapsed > REQUEST_THRESHOLD => reasons.push(format!(
+                    "{queue_size} requests queued, but none processed for {:.1}s",
+                    elapsed.as_secs_f64()
+                )),
+                Some(_) => {}
+            }
+        }
+
+        let status = if reasons.is_empty() {
+            HealthStatus::Serving
+        } else {
+            HealthStatus::NotServing
+        };
+
+        GetHealthResponse {
+            alive: true,
+            status: status.into(),
+            reasons,
+            seconds_since_last_frame: self.since_last_frame.map(|d| d.as_secs_f64()),
+            seconds_since_last_request: self.since_last_request.map(|d| d.as_secs_f64()),
+            queue_size: self.queue_size,
+            srs_connected: self.srs_connected,
+            mission_loaded: self.mission_loaded,
+            paused: self.paused,
+        }
+    }
+}
+
+/// This guard is used to keep track of the number of open connections to SRS.
+pub struct TrackSrsConnectionGuard {
+    health: Arc<Inner>,
+}
+
+impl Drop for TrackSrsConnectionGuard {
+    fn drop(&mut self) {
+        self.health.srs_connections.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Periodically probes the mission and the hook environment and reports the result to the
+/// standard `grpc.health.v1.Health` service. Services are reported as `SERVING` if the Lua
+/// environment they are executed in answers requests. The overall server status (empty service
+/// name) follows the verdict of [Health::report].
+pub async fn run_in_background(
+    health: Health,
+    mission_rpc: MissionRpc,
+    hook_rpc: HookRpc,
+    reporter: HealthReporter,
+    mut shutdown_signal: impl Future<Output = ()> + Unpin,
+) {
+    let mut interval = interval(PROBE_INTERVAL);
+    interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+    let mut mission_probe = Probe::default();
+    let mut hook_probe = Probe::default();
+
+    loop {
+        // wait for either the shutdown signal or the next interval tick, whatever happens first
+        tokio::select! {
+            _ = &mut shutdown_signal => {
+                break
+            }
+            _ = interval.tick() => {}
+        };
+
+        let (mission, hook) = futures_util::future::join(
+            mission_probe.probe(|| {
+                let rpc = mission_rpc.clone();
+                Box::pin(async move {
+                    rpc.request::<_, GetTimeResponse>("getTime", Request::new(GetTimeRequest {}))
+                        .await
+                })
+            }),
+            hook_probe.probe(|| {
+                let rpc = hook_rpc.clone();
+                Box::pin(async move {
+                    rpc.request::<_, GetPausedResponse>(
+                        "getPaused",
+                        Request::new(GetPausedRequest {}),
+                    )
+                    .await
+                })
+            }),
+        )
+        .await;
+
+        let mission_serving = mission.is_some();
+        let paused = hook.as_ref().map(|res| res.paused).unwrap_or(false);
+        health
+            .0
+            .mission_loaded
+            .store(mission_serving, Ordering::Relaxed);
+        health.0.paused.store(paused, Ordering::Relaxed);
+
+        for service in MISSION_SERVICES {
+            reporter
+                .set_service_status(*service, serving_status(mission_serving))
+                .await;
+        }
+        for service in HOOK_SERVICES {
+            reporter
+                .set_service_status(*service, serving_status(hook.is_some()))
+                .await;
+        }
+        let serving = health.report().status() == HealthStatus::Serving;
+        reporter
+            .set_service_status("", serving_status(serving))
+            .await;
+    }
+}
+
+/// Milliseconds since [START]; never `0` so that `0` can be used to represent "never".
+fn now() -> u64 {
+    u64::try_from(START.elapsed().as_millis())
+        .unwrap_or(u64::MAX)
+        .max(1)
+}
+
+fn since(millis: u64) -> Option<Duration> {
+    if millis == 0 {
+        return None;
+    }
+    Some(
+        START
+            .elapsed()
+            .saturating_sub(Duration::from_millis(millis)),
+    )
+}
+
+fn serving_status(serving: bool) -> ServingStatus {
+    if serving {
+        ServingStatus::Serving
+    } else {
+        ServingStatus::NotServing
+    }
+}
+
+/// A probe request into one of the Lua environments. A request that is not answered within the
+/// [PROBE_TIMEOUT] is kept and awaited again on the next tick instead of queueing yet another one,
+/// as the environment would otherwise receive all of them at once once it becomes responsive again.
+struct Probe<T> {
+    pending: Option<BoxFuture<'static, Result<T, Status>>>,
+}
+
+impl<T> Default for Probe<T> {
+    fn default() -> Self {
+        Self { pending: None }
+    }
+}
+
+impl<T> Probe<T> {
+    async fn probe(
+        &mut self,
+        request: impl FnOnce() -> BoxFuture<'static, Result<T, Status>>,
+    ) -> Option<T> {
+        let pending = self.pending.get_or_insert_with(request);
+        match timeout(PROBE_TIMEOUT, pending).await {
+            Ok(res) => {
+                self.pending = None;
+                match res {
+                    Ok(res) => Some(res),
+                    Err(err) => {
+                        log::debug!("health probe failed: {}", err);
+                        None
+                    }
+                }
+            }
+            Err(_) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Signals of a healthy server.
+    fn healthy() -> Signals {
+        Signals {
+            since_last_frame: Some(Duration::from_millis(16)),
+            since_last_request: Some(Duration::from_secs(1)),
+            queue_size: 0,
+            srs_connected: false,
+            mission_loaded: true,
+            paused: false,
+        }
+    }
+
+    #[test]
+    fn test_serving() {
+        let report = healthy().report();
+        assert_eq!(report.status(), HealthStatus::Serving);
+        assert!(report.reasons.is_empty());
+        assert!(report.alive);
+        assert_eq!(report.seconds_since_last_frame, Some(0.016));
+
+        // requests being queued is fine as long as they are processed
+        let report = Signals {
+            queue_size: 5,
+            ..healthy()
+        }
+        .report();
+        assert_eq!(report.status(), HealthStatus::Serving);
+
+        // no request processed for a long time is fine as long as none are queued
+        let report = Signals {
+            since_last_request: Some(Duration::from_secs(3600)),
+            ..healthy()
+        }
+        .report();
+        assert_eq!(report.status(), HealthStatus::Serving);
+    }
+
+    #[test]
+    fn test_stale_frame() {
+        let report = Signals {
+            since_last_frame: Some(FRAME_THRESHOLD + Duration::from_millis(500)),
+            ..healthy()
+        }
+        .report();
+        assert_eq!(report.status(), HealthStatus::NotServing);
+        assert_eq!(
+            report.reasons,
+            vec!["no simulation frame received for 10.5s".to_string()]
+        );
+
+        let report = Signals {
+            since_last_frame: None,
+            ..healthy()
+        }
+        .report();
+        assert_eq!(report.status(), HealthStatus::NotServing);
+        assert_eq!(
+            report.reasons,
+            vec!["no simulation frame received yet".to_string()]
+        );
+        assert_eq!(report.seconds_since_last_frame, None);
+    }
+
+    #[test]
+    fn test_stale_request() {
+        let report = Signals {
+            since_last_request: Some(REQUEST_THRESHOLD + Duration::from_secs(2)),
+            queue_size: 3,
+            ..healthy()
+        }
+        .report();
+        assert_eq!(report.status(), HealthStatus::NotServing);
+        assert_eq!(
+            report.reasons,
+            vec!["3 requests queued, but none processed for 12.0s".to_string()]
+        );
+
+        let report = Signals {
+            since_last_request: None,
+            queue_size: 1,
+            ..healthy()
+        }
+        .report();
+        assert_eq!(report.status(), HealthStatus::NotServing);
+        assert_eq!(
+            report.reasons,
+            vec!["1 requests queued, but none processed yet".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_paused() {
+        let report = Signals {
+            paused: true,
+            ..healthy()
+        }
+        .report();
+        assert_eq!(report.status(), HealthStatus::NotServing);
+        assert_eq!(report.reasons, vec!["mission is paused".to_string()]);
+        assert!(report.paused);
+    }
+
+    #[test]
+    fn test_no_mission() {
+        let report = Signals {
+            mission_loaded: false,
+            since_last_frame: None,
+            ..healthy()
+        }
+        .report();
+        assert_eq!(report.status(), HealthStatus::NotServing);
+        assert_eq!(
+            report.reasons,
+            vec![
+                "mission environment does not answer requests".to_string(),
+                "no simulation frame received yet".to_string(),
+            ]
+        );
+        assert!(!report.mission_loaded);
+    }
+}
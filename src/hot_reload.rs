@@ -85,6 +85,44 @@ pub fn event(lua: &Lua, event: Value) -> LuaResult<()> {
     }
 }
 
+// Hook events emitted before the first `start()` are discarded, as they can only be kept by the
+// dynamically loaded lib.
+pub fn hook_event(lua: &Lua, event: Value) -> LuaResult<()> {
+    if let Some(ref lib) = *LIBRARY.read().unwrap() {
+        let f: Symbol<fn(lua: &Lua, event: Value) -> LuaResult<()>> = unsafe {
+            lib.get(b"hook_event")
+                .map_err(|err| mlua::Error::ExternalError(Arc::new(err)))?
+        };
+        f(lua, event).map_err(take_error_ownership)
+    } else {
+        Ok(())
+    }
+}
+
+pub fn gatekeep(lua: &Lua, decision: Value) -> LuaResult<Value> {
+    if let Some(ref lib) = *LIBRARY.read().unwrap() {
+        let f: Symbol<fn(lua: &Lua, decision: Value) -> LuaResult<Value>> = unsafe {
+            lib.get(b"gatekeep")
+                .map_err(|err| mlua::Error::ExternalError(Arc::new(err)))?
+        };
+        f(lua, decision).map_err(take_error_ownership)
+    } else {
+        Ok(Value::Nil)
+    }
+}
+
+pub fn chat_command(lua: &Lua, msg: Value) -> LuaResult<Value> {
+    if let Some(ref lib) = *LIBRARY.read().unwrap() {
+        let f: Symbol<fn(lua: &Lua, msg: Value) -> LuaResult<Value>> = unsafe {
+            lib.get(b"chat_command")
+                .map_err(|err| mlua::Error::ExternalError(Arc::new(err)))?
+        };
+        f(lua, msg).map_err(take_error_ownership)
+    } else {
+        Ok(Value::Nil)
+    }
+}
+
 pub fn simulation_frame(lua: &Lua, time: f64) -> LuaResult<()> {
     if let Some(ref lib) = *LIBRARY.read().unwrap() {
         let f: Symbol<fn(lua: &Lua, time: f64) -> LuaResult<()>> = unsafe {
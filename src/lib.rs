@@ -4,31 +4,48 @@
 mod authentication;
 mod config;
 mod fps;
+mod gateway;
+mod health;
 #[cfg(feature = "hot-reload")]
 mod hot_reload;
 mod integrity;
 pub mod rpc;
 mod server;
 mod shutdown;
+mod sink;
 mod srs;
 mod stats;
 mod stream;
+mod webhook;
+mod websocket;
+mod zone;
 
+use std::collections::VecDeque;
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::{Arc, RwLock};
+use std::sync::{Arc, Mutex, RwLock};
 use std::time::Instant;
 
 use config::Config;
 use mlua::prelude::*;
 use mlua::{Function, LuaSerdeExt, Value};
 use once_cell::sync::Lazy;
+use rpc::{ChatMessage, Verdict};
 use server::{Server, TtsOptions};
+use stubs::gatekeeper::v0::decide_response::Decision;
 use stubs::mission::v0::StreamEventsResponse;
+use stubs::mission::v0::stream_events_response::Event;
 use thiserror::Error;
 
 static INITIALIZED: AtomicBool = AtomicBool::new(false);
 static SERVER: Lazy<RwLock<Option<Server>>> = Lazy::new(|| RwLock::new(None));
+/// Events of the hook environment emitted while the server isn't running (e.g. the
+/// `MissionLoadBeginEvent`, as the server is only started by the mission environment afterwards).
+/// They are published right before the next hook event once the server is running.
+static PENDING_HOOK_EVENTS: Lazy<Mutex<VecDeque<StreamEventsResponse>>> =
+    Lazy::new(|| Mutex::new(VecDeque::new()));
+/// The maximum number of hook events kept while the server isn't running.
+const MAX_PENDING_HOOK_EVENTS: usize = 32;
 
 pub fn init(config: &Config) {
     if INITIALIZED
@@ -136,6 +153,7 @@ pub fn next(lua: &Lua, (env, callback): (i32, Function)) -> LuaResult<bool> {
 
         if let Some(mut next) = next {
             server.stats().track_call();
+            server.health().track_request();
 
             let method = next.method().to_string();
             #[allow(clippy::arc_with_non_send_sync)]
@@ -221,11 +239,114 @@ pub fn event(lua: &Lua, event: Value) -> LuaResult<()> {
     Ok(())
 }
 
+/// Emit an event of the hook environment. Other than [event], this is available before the
+/// mission environment started the server. Events emitted while the server isn't running are kept
+/// (see [PENDING_HOOK_EVENTS]) instead of being discarded.
+#[unsafe(no_mangle)]
+pub fn hook_event(lua: &Lua, event: Value) -> LuaResult<()> {
+    let start = Instant::now();
+
+    let event: StreamEventsResponse = match lua.from_value(event) {
+        Ok(event) => event,
+        Err(err) => {
+            log::error!("failed to deserialize hook event: {}", err);
+            return Ok(());
+        }
+    };
+
+    let mut pending = PENDING_HOOK_EVENTS.lock().unwrap();
+    if let Some(server) = &*SERVER.read().unwrap() {
+        let _guard = server.stats().track_block_time(start);
+        for event in pending.drain(..).chain(std::iter::once(event)) {
+            server.stats().track_event();
+            log::debug!("Received hook event: {:#?}", event);
+            server.block_on(server.ipc_mission().event(event));
+        }
+        return Ok(());
+    }
+
+    // a new mission starts loading, so all events kept so far belong to a mission the server
+    // didn't run for
+    if matches!(event.event, Some(Event::MissionLoadBegin(_))) {
+        pending.clear();
+    }
+    if pending.len() >= MAX_PENDING_HOOK_EVENTS {
+        pending.pop_front();
+    }
+    pending.push_back(event);
+
+    Ok(())
+}
+
+/// Ask the connected gatekeepers whether a player is allowed to connect or to change into a slot.
+/// Players with an active ban are denied without asking the gatekeepers. Otherwise, blocks until
+/// all gatekeepers answered or the configured timeout elapsed. Returns `nil` if no gatekeeper is
+/// connected.
+#[unsafe(no_mangle)]
+pub fn gatekeep(lua: &Lua, decision: Value) -> LuaResult<Value> {
+    let start = Instant::now();
+
+    let decision: Decision = match lua.from_value(decision) {
+        Ok(decision) => decision,
+        Err(err) => {
+            // log instead of returning the error to not interfere with the player joining
+            log::error!("failed to deserialize gatekeeper decision: {}", err);
+            return Ok(Value::Nil);
+        }
+    };
+
+    if let Some(server) = &*SERVER.read().unwrap() {
+        let _guard = server.stats().track_block_time(start);
+        if let Decision::TryConnect(player) = &decision {
+            if let Some(ban) = server.bans().find_active(&player.ucid, &player.addr) {
+                log::info!("Denied banned player `{}` to connect", player.name);
+                return lua.to_value_with(
+                    &Verdict {
+                        allow: false,
+                        reason: Some(format!("Banned: {}", ban.reason)),
+                    },
+                    lua_serialize_options(),
+                );
+            }
+        }
+        if let Some(verdict) = server.block_on(server.gatekeeper().decide(decision)) {
+            return lua.to_value_with(&verdict, lua_serialize_options());
+        }
+    }
+
+    Ok(Value::Nil)
+}
+
+/// Forward a chat message to the `ChatCommandService` clients with a matching command. Returns
+/// `nil` if the message didn't match any command.
+#[unsafe(no_mangle)]
+pub fn chat_command(lua: &Lua, msg: Value) -> LuaResult<Value> {
+    let start = Instant::now();
+
+    let msg: ChatMessage = match lua.from_value(msg) {
+        Ok(msg) => msg,
+        Err(err) => {
+            log::error!("failed to deserialize chat message: {}", err);
+            return Ok(Value::Nil);
+        }
+    };
+
+    if let Some(server) = &*SERVER.read().unwrap() {
+        let _guard = server.stats().track_block_time(start);
+        if let Some(result) = server.chat_commands().dispatch(msg) {
+            return lua.to_value_with(&result, lua_serialize_options());
+        }
+    }
+
+    Ok(Value::Nil)
+}
+
 // This method is called on each simulation frame, so make sure to do as few as possible (avoid
 // even getting a lock on [SERVER]).
 #[unsafe(no_mangle)]
 pub fn simulation_frame(_lua: &Lua, time: f64) -> LuaResult<()> {
     crate::fps::frame(time);
+    crate::health::track_frame();
 
     Ok(())
 }
@@ -277,6 +398,12 @@ pub fn dcs_grpc_hot_reload(lua: &Lua) -> LuaResult<LuaTable> {
     exports.set("stop", lua.create_function(hot_reload::stop)?)?;
     exports.set("next", lua.create_function(hot_reload::next)?)?;
     exports.set("event", lua.create_function(hot_reload::event)?)?;
+    exports.set("hookEvent", lua.create_function(hot_reload::hook_event)?)?;
+    exports.set("gatekeep", lua.create_function(hot_reload::gatekeep)?)?;
+    exports.set(
+        "chatCommand",
+        lua.create_function(hot_reload::chat_command)?,
+    )?;
     exports.set(
         "simulationFrame",
         lua.create_function(hot_reload::simulation_frame)?,
@@ -297,6 +424,9 @@ pub fn dcs_grpc(lua: &Lua) -> LuaResult<LuaTable> {
     exports.set("stop", lua.create_function(stop)?)?;
     exports.set("next", lua.create_function(next)?)?;
     exports.set("event", lua.create_function(event)?)?;
+    exports.set("hookEvent", lua.create_function(hook_event)?)?;
+    exports.set("gatekeep", lua.create_function(gatekeep)?)?;
+    exports.set("chatCommand", lua.create_function(chat_command)?)?;
     exports.set("simulationFrame", lua.create_function(simulation_frame)?)?;
     exports.set("tts", lua.create_function(tts)?)?;
     exports.set("logError", lua.create_function(log_error)?)?;
@@ -306,6 +436,12 @@ pub fn dcs_grpc(lua: &Lua) -> LuaResult<LuaTable> {
     Ok(exports)
 }
 
+/// Options for serializing results returned to Lua. `None` is serialized as `nil` instead of a
+/// `null` light userdata, which would be truthy in Lua.
+fn lua_serialize_options() -> LuaSerializeOptions {
+    LuaSerializeOptions::new().serialize_none_to_null(false)
+}
+
 fn pretty_print_value(val: Value, indent: usize) -> LuaResult<String> {
     use std::fmt::Write;
 
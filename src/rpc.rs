@@ -1,35 +1,52 @@
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use dcs_module_ipc::IPC;
 use futures_util::Stream;
 use stubs::mission::v0::StreamEventsResponse;
 use tokio::sync::RwLock;
+use tokio::sync::mpsc::{self, Receiver};
 use tonic::{Request, Status};
 
+pub use self::ban::Bans;
+pub use self::chat::{ChatCommands, ChatMessage};
+pub use self::gatekeeper::{Gatekeeper, Verdict};
+pub use self::player::Players;
 pub use self::srs::Srs;
+use crate::config::StreamsConfig;
+use crate::health::Health;
 use crate::shutdown::ShutdownHandle;
 use crate::stats::Stats;
+use crate::stream::{Lagged, Subscriber};
 
 mod atmosphere;
+mod ban;
+mod chat;
 mod coalition;
 mod controller;
 mod custom;
+mod gatekeeper;
 mod group;
 mod hook;
+mod land;
 mod metadata;
 mod mission;
 mod net;
+mod player;
 mod srs;
 mod timer;
 mod trigger;
 mod unit;
 mod world;
+mod zone;
 
 #[derive(Clone)]
 pub struct MissionRpc {
     ipc: IPC<StreamEventsResponse>,
     stats: Stats,
+    health: Health,
     eval_enabled: bool,
+    streams_config: StreamsConfig,
     shutdown_signal: ShutdownHandle,
     cache: Arc<RwLock<Cache>>,
 }
@@ -51,12 +68,15 @@ impl MissionRpc {
     pub fn new(
         ipc: IPC<StreamEventsResponse>,
         stats: Stats,
+        health: Health,
         shutdown_signal: ShutdownHandle,
     ) -> Self {
         MissionRpc {
             ipc,
             stats,
+            health,
             eval_enabled: false,
+            streams_config: StreamsConfig::default(),
             shutdown_signal,
             cache: Default::default(),
         }
@@ -66,6 +86,14 @@ impl MissionRpc {
         self.eval_enabled = true;
     }
 
+    pub fn set_streams_config(&mut self, config: StreamsConfig) {
+        self.streams_config = config;
+    }
+
+    pub fn health(&self) -> &Health {
+        &self.health
+    }
+
     pub async fn request<I, O>(&self, method: &str, request: Request<I>) -> Result<O, Status>
     where
         I: serde::Serialize + Send + Sync + 'static,
@@ -78,6 +106,18 @@ impl MissionRpc {
             .map_err(to_status)
     }
 
+    /// Creates the stream to a new subscriber, buffered as configured in `streams` and tracked in
+    /// the stats under the given `name`.
+    pub fn subscribe<T: Lagged>(
+        &self,
+        name: String,
+    ) -> (Subscriber<T>, Receiver<Result<T, Status>>) {
+        let (tx, rx) = mpsc::channel(self.streams_config.event_buffer.max(1));
+        let subscriber =
+            Subscriber::new(tx, &self.streams_config, self.stats.track_subscriber(name));
+        (subscriber, rx)
+    }
+
     pub async fn events(&self) -> impl Stream<Item = StreamEventsResponse> + use<> {
         let ipc = self.ipc.clone();
         ipc.events().await
@@ -129,3 +169,21 @@ fn to_status(err: dcs_module_ipc::Error) -> Status {
         err => Status::internal(err.to_string()),
     }
 }
+
+/// Move a file that couldn't be loaded out of the way (suffixed with `.invalid-{timestamp}`), so
+/// that it isn't overwritten with an empty state the next time it is written.
+fn move_aside(path: &Path) {
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+    let mut target = path.as_os_str().to_owned();
+    target.push(format!(".invalid-{timestamp}"));
+    let target = PathBuf::from(target);
+
+    match std::fs::rename(path, &target) {
+        Ok(()) => log::warn!("Moved `{}` to `{}`", path.display(), target.display()),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+        Err(err) => log::error!("Failed to move `{}` aside: {}", path.display(), err),
+    }
+}
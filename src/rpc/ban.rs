@@ -0,0 +1,343 @@
+use std::net::{IpAddr, SocketAddr};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use stubs::ban;
+use stubs::ban::v0::Ban;
+use stubs::ban::v0::ban_service_server::BanService;
+use tonic::{Request, Response, Status};
+
+use crate::authentication::AuthenticatedClient;
+
+/// A ban registry persisted as JSON file, enforced when players try to connect.
+#[derive(Clone)]
+pub struct Bans(Arc<Inner>);
+
+struct Inner {
+    path: PathBuf,
+    /// The id given to the next ban.
+    next_id: AtomicU64,
+    bans: Mutex<Vec<Ban>>,
+    /// Serializes writes to the file.
+    write_lock: tokio::sync::Mutex<()>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+struct BansFile {
+    bans: Vec<Ban>,
+}
+
+impl Bans {
+    /// Load the bans from the given file (starting with an empty registry if the file doesn't
+    /// exist yet). A file that can't be loaded doesn't prevent the server from starting; it is
+    /// moved aside and the registry starts empty instead.
+    pub fn load(path: PathBuf) -> Self {
+        let bans = match read(&path) {
+            Ok(bans) => bans,
+            Err(err) => {
+                log::error!(
+                    "Failed to load bans from `{}`, starting without any bans: {}",
+                    path.display(),
+                    err
+                );
+                super::move_aside(&path);
+                Vec::new()
+            }
+        };
+        log::info!("Loaded {} bans from `{}`", bans.len(), path.display());
+
+        let next_id = bans.iter().map(|ban| ban.id).max().unwrap_or(0) + 1;
+        Self(Arc::new(Inner {
+            path,
+            next_id: AtomicU64::new(next_id),
+            bans: Mutex::new(bans),
+            write_lock: Default::default(),
+        }))
+    }
+
+    /// Find an active ban for the player's UCID or address (IP with or without port).
+    pub fn find_active(&self, ucid: &str, addr: &str) -> Option<Ban> {
+        let ip = addr
+            .parse::<SocketAddr>()
+            .map(|addr| addr.ip())
+            .or_else(|_| addr.parse::<IpAddr>())
+            .ok();
+        let now = now();
+        self.0
+            .bans
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|ban| {
+                let banned_ip = ban.ip_address.as_deref().and_then(|ip| ip.parse().ok());
+                is_active(ban, now)
+                    && (ban.ucid.as_deref() == Some(ucid) || (ip.is_some() && banned_ip == ip))
+            })
+            .cloned()
+    }
+
+    fn filter(&self, include_expired: bool, predicate: impl Fn(&Ban) -> bool) -> Vec<Ban> {
+        let now = now();
+        self.0
+            .bans
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|ban| (include_expired || is_active(ban, now)) && predicate(ban))
+            .cloned()
+            .collect()
+    }
+
+    /// Write all bans to the file. The file is replaced atomically, so that a crash while writing
+    /// doesn't corrupt it.
+    async fn save(&self) -> Result<(), Status> {
+        let _lock = self.0.write_lock.lock().await;
+        let json = {
+            let bans = self.0.bans.lock().unwrap();
+            serde_json::to_vec_pretty(&BansFile { bans: bans.clone() })
+        }
+        .map_err(|err| Status::internal(format!("failed to serialize bans: {err}")))?;
+
+        let tmp = self.0.path.with_extension("json.tmp");
+        let write = async {
+            if let Some(dir) = self.0.path.parent() {
+                tokio::fs::create_dir_all(dir).await?;
+            }
+            tokio::fs::write(&tmp, json).await?;
+            tokio::fs::rename(&tmp, &self.0.path).await
+        };
+        write.await.map_err(|err| {
+            log::error!(
+                "Failed to write bans to `{}`: {}",
+                self.0.path.display(),
+                err
+            );
+            Status::internal(format!("failed to persist bans: {err}"))
+        })
+    }
+}
+
+#[tonic::async_trait]
+impl BanService for Bans {
+    async fn add_ban(
+        &self,
+        request: Request<ban::v0::AddBanRequest>,
+    ) -> Result<Response<ban::v0::AddBanResponse>, Status> {
+        let issuer = issuer(&request);
+        let request = request.into_inner();
+        if request.ucid.is_none() && request.ip_address.is_none() {
+            return Err(Status::invalid_argument(
+                "either the ucid or the ip_address is required",
+            ));
+        }
+        if let Some(ip) = &request.ip_address {
+            ip.parse::<IpAddr>().map_err(|err| {
+                Status::invalid_argument(format!("invalid ip_address `{ip}`: {err}"))
+            })?;
+        }
+
+        let now = now();
+        let banned_until = match request.period {
+            Some(period) => Some(now.checked_add(period).ok_or_else(|| {
+                Status::invalid_argument(format!("period of {period}s is out of range"))
+            })?),
+            None => None,
+        };
+        let ban = Ban {
+            id: self.0.next_id.fetch_add(1, Ordering::Relaxed),
+            ucid: request.ucid,
+            ip_address: request.ip_address,
+            player_name: request.player_name,
+            reason: request.reason,
+            issuer,
+            banned_from: now,
+            banned_until,
+            expired_by: None,
+        };
+        log::info!("Added ban: {:?}", ban);
+        self.0.bans.lock().unwrap().push(ban.clone());
+        self.save().await?;
+
+        Ok(Response::new(ban::v0::AddBanResponse { ban: Some(ban) }))
+    }
+
+    async fn list_bans(
+        &self,
+        request: Request<ban::v0::ListBansRequest>,
+    ) -> Result<Response<ban::v0::ListBansResponse>, Status> {
+        let bans = self.filter(request.into_inner().include_expired, |_| true);
+        Ok(Response::new(ban::v0::ListBansResponse { bans }))
+    }
+
+    async fn search_bans(
+        &self,
+        request: Request<ban::v0::SearchBansRequest>,
+    ) -> Result<Response<ban::v0::SearchBansResponse>, Status> {
+        let request = request.into_inner();
+        let query = request.query.to_lowercase();
+        let contains =
+            |field: Option<&str>| field.is_some_and(|f| f.to_lowercase().contains(&query));
+        let bans = self.filter(request.include_expired, |ban| {
+            contains(ban.ucid.as_deref())
+                || contains(ban.ip_address.as_deref())
+                || contains(ban.player_name.as_deref())
+                || contains(Some(&ban.reason))
+        });
+        Ok(Response::new(ban::v0::SearchBansResponse { bans }))
+    }
+
+    async fn expire_ban(
+        &self,
+        request: Request<ban::v0::ExpireBanRequest>,
+    ) -> Result<Response<ban::v0::ExpireBanResponse>, Status> {
+        let issuer = issuer(&request);
+        let id = request.into_inner().id;
+        let ban = {
+            let mut bans = self.0.bans.lock().unwrap();
+            let ban = bans
+                .iter_mut()
+                .find(|ban| ban.id == id)
+                .ok_or_else(|| Status::not_found(format!("ban {id} not found")))?;
+            let now = now();
+            if !is_active(ban, now) {
+                return Err(Status::failed_precondition(format!(
+                    "ban {id} already expired"
+                )));
+            }
+            ban.banned_until = Some(now);
+            ban.expired_by = Some(issuer);
+            ban.clone()
+        };
+        log::info!("Expired ban: {:?}", ban);
+        self.save().await?;
+
+        Ok(Response::new(ban::v0::ExpireBanResponse { ban: Some(ban) }))
+    }
+}
+
+fn read(path: &Path) -> Result<Vec<Ban>, std::io::Error> {
+    match std::fs::read(path) {
+        Ok(json) => Ok(serde_json::from_slice::<BansFile>(&json)
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))?
+            .bans),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(err) => Err(err),
+    }
+}
+
+fn issuer<T>(request: &Request<T>) -> String {
+    request
+        .extensions()
+        .get::<AuthenticatedClient>()
+        .map(|client| client.0.clone())
+        .unwrap_or_default()
+}
+
+fn is_active(ban: &Ban, now: u64) -> bool {
+    ban.banned_until.is_none_or(|until| until > now)
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use stubs::ban::v0::AddBanRequest;
+
+    use super::*;
+
+    /// A fresh directory per test, as tests run in parallel.
+    fn test_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("dcs-grpc-bans-{}-{}", std::process::id(), name));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn add_request(ucid: &str, period: Option<u64>) -> Request<AddBanRequest> {
+        Request::new(AddBanRequest {
+            ucid: Some(ucid.to_string()),
+            ip_address: Some("10.0.0.1".to_string()),
+            player_name: None,
+            reason: "Teamkilling".to_string(),
+            period,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_add_and_reload() {
+        let dir = test_dir("reload");
+        let path = dir.join("bans.json");
+
+        let bans = Bans::load(path.clone());
+        bans.add_ban(add_request("abc", None)).await.unwrap();
+        bans.add_ban(add_request("def", Some(3600))).await.unwrap();
+
+        let bans = Bans::load(path);
+        assert!(bans.find_active("abc", "192.168.0.1:10308").is_some());
+        assert!(bans.find_active("xyz", "10.0.0.1:10308").is_some());
+        assert!(bans.find_active("xyz", "10.0.0.1").is_some());
+        assert!(bans.find_active("xyz", "192.168.0.1:10308").is_none());
+        assert_eq!(bans.0.next_id.load(Ordering::Relaxed), 3);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_add_period_out_of_range() {
+        let dir = test_dir("overflow");
+        let bans = Bans::load(dir.join("bans.json"));
+
+        let err = bans
+            .add_ban(add_request("abc", Some(u64::MAX)))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), tonic::Code::InvalidArgument);
+        assert!(bans.0.bans.lock().unwrap().is_empty());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_load_invalid_file() {
+        let dir = test_dir("invalid");
+        let path = dir.join("bans.json");
+        std::fs::write(&path, "{ not json").unwrap();
+
+        let bans = Bans::load(path.clone());
+        assert!(bans.0.bans.lock().unwrap().is_empty());
+
+        // the invalid file is kept, but moved out of the way
+        assert!(!path.exists());
+        let moved = std::fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(moved.len(), 1);
+        assert!(moved[0].starts_with("bans.json.invalid-"));
+
+        // the registry is usable nonetheless
+        bans.add_ban(add_request("abc", None)).await.unwrap();
+        assert!(Bans::load(path).find_active("abc", "").is_some());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_expired_ban_is_inactive() {
+        let ban = Ban {
+            banned_until: Some(100),
+            ..Default::default()
+        };
+        assert!(is_active(&ban, 99));
+        assert!(!is_active(&ban, 100));
+        assert!(is_active(&Ban::default(), u64::MAX));
+    }
+}
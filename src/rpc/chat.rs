@@ -0,0 +1,404 @@
+use std::collections::{BTreeMap, HashMap};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use futures_util::{Stream, StreamExt};
+use prost_types::value::Kind;
+use stubs::chat::v0::chat_command_service_server::ChatCommandService;
+use stubs::chat::v0::{ArgumentType, Command, ListenRequest, ListenResponse};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::{Request, Response, Status, Streaming};
+
+use crate::shutdown::{AbortableStream, ShutdownHandle};
+
+/// Matches chat messages against the commands registered by `ChatCommandService` clients and
+/// forwards the invocations to them.
+#[derive(Clone)]
+pub struct ChatCommands(Arc<Inner>);
+
+struct Inner {
+    shutdown_signal: ShutdownHandle,
+    /// The id given to the next listener.
+    next_id: AtomicU64,
+    /// All currently connected listeners (by listener id).
+    listeners: Mutex<HashMap<u64, Listener>>,
+}
+
+struct Listener {
+    commands: Vec<Command>,
+    tx: mpsc::Sender<Result<ListenResponse, Status>>,
+}
+
+/// A chat message as received from Lua.
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChatMessage {
+    player_id: u32,
+    player_name: String,
+    ucid: String,
+    coalition: i32,
+    message: String,
+}
+
+/// The result returned to Lua for a chat message that matched at least one command.
+#[derive(Debug, Default, serde::Serialize)]
+pub struct ChatCommandResult {
+    /// Whether the chat message should be hidden from the public chat.
+    suppress: bool,
+    /// A message sent back to the player (e.g. the usage of a command after invalid arguments).
+    // skipped instead of serialized as `null`, which would be truthy in Lua
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reply: Option<String>,
+}
+
+impl ChatCommands {
+    pub fn new(shutdown_signal: ShutdownHandle) -> Self {
+        Self(Arc::new(Inner {
+            shutdown_signal,
+            next_id: AtomicU64::new(1),
+            listeners: Default::default(),
+        }))
+    }
+
+    /// Forward the chat message to all listeners with a matching command. Returns `None` if the
+    /// message didn't match any command.
+    pub fn dispatch(&self, msg: ChatMessage) -> Option<ChatCommandResult> {
+        let message = msg.message.trim_start();
+        let (invoked, rest) = message
+            .split_once(char::is_whitespace)
+            .unwrap_or((message, ""));
+
+        let mut result: Option<ChatCommandResult> = None;
+        let listeners = self.0.listeners.lock().unwrap();
+        for listener in listeners.values() {
+            for command in &listener.commands {
+                if !invokes(command, invoked) {
+                    continue;
+                }
+
+                let result = result.get_or_insert_with(Default::default);
+                result.suppress |= command.suppress;
+
+                let arguments = match parse_arguments(command, rest) {
+                    Ok(arguments) => arguments,
+                    Err(err) => {
+                        result.reply = Some(format!("{err} (usage: {})", usage(command)));
+                        continue;
+                    }
+                };
+
+                let invocation = ListenResponse {
+                    command: command.name.clone(),
+                    player_id: msg.player_id,
+                    player_name: msg.player_name.clone(),
+                    ucid: msg.ucid.clone(),
+                    coalition: msg.coalition,
+                    arguments: Some(arguments),
+                    message: msg.message.clone(),
+                };
+                if listener.tx.try_send(Ok(invocation)).is_err() {
+                    log::warn!(
+                        "Dropped invocation of chat command `{}` for not keeping up",
+                        command.name
+                    );
+                }
+            }
+        }
+
+        result
+    }
+}
+
+#[tonic::async_trait]
+impl ChatCommandService for ChatCommands {
+    type ListenStream =
+        Pin<Box<dyn Stream<Item = Result<ListenResponse, Status>> + Send + Sync + 'static>>;
+
+    async fn listen(
+        &self,
+        request: Request<Streaming<ListenRequest>>,
+    ) -> Result<Response<Self::ListenStream>, Status> {
+        let mut requests = request.into_inner();
+        let (tx, rx) = mpsc::channel(64);
+        let listener_id = self.0.next_id.fetch_add(1, Ordering::Relaxed);
+        self.0.listeners.lock().unwrap().insert(
+            listener_id,
+            Listener {
+                commands: Vec::new(),
+                tx: tx.clone(),
+            },
+        );
+
+        let chat_commands = self.clone();
+        tokio::spawn(async move {
+            // update the registered commands until the listener disconnects
+            while let Some(req) = requests.next().await {
+                let commands = match req {
+                    Ok(req) => req.commands,
+                    Err(err) => {
+                        log::debug!("Chat command listener {} failed: {}", listener_id, err);
+                        break;
+                    }
+                };
+                if let Err(err) = validate(&commands) {
+                    // ignore error, as we don't care at this point whether the channel is closed
+                    // or not
+                    let _ = tx.send(Err(Status::invalid_argument(err))).await;
+                    break;
+                }
+                if let Some(listener) = chat_commands
+                    .0
+                    .listeners
+                    .lock()
+                    .unwrap()
+                    .get_mut(&listener_id)
+                {
+                    listener.commands = commands;
+                }
+            }
+
+            chat_commands
+                .0
+                .listeners
+                .lock()
+                .unwrap()
+                .remove(&listener_id);
+        });
+
+        let stream = AbortableStream::new(self.0.shutdown_signal.signal(), ReceiverStream::new(rx));
+        Ok(Response::new(Box::pin(stream)))
+    }
+}
+
+fn validate(commands: &[Command]) -> Result<(), String> {
+    for command in commands {
+        if command.name.is_empty() || command.name.contains(char::is_whitespace) {
+            return Err(format!(
+                "invalid command name `{}` (must not be empty or contain whitespace)",
+                command.name
+            ));
+        }
+        if command.prefix().contains(char::is_whitespace) {
+            return Err(format!(
+                "prefix of command `{}` must not contain whitespace",
+                command.name
+            ));
+        }
+
+        let mut optional = false;
+        for (i, arg) in command.arguments.iter().enumerate() {
+            if arg.r#type() == ArgumentType::Rest && i + 1 < command.arguments.len() {
+                return Err(format!(
+                    "argument `{}` of command `{}` is of type rest, but isn't the last argument",
+                    arg.name, command.name
+                ));
+            }
+            if optional && !arg.optional {
+                return Err(format!(
+                    "required argument `{}` of command `{}` follows an optional argument",
+                    arg.name, command.name
+                ));
+            }
+            optional = arg.optional;
+        }
+    }
+
+    Ok(())
+}
+
+fn invokes(command: &Command, invoked: &str) -> bool {
+    invoked
+        .strip_prefix(command.prefix.as_deref().unwrap_or("-"))
+        .is_some_and(|name| name.eq_ignore_ascii_case(&command.name))
+}
+
+/// Parse the arguments (the chat message after the command) according to the command's
+/// arguments.
+fn parse_arguments(command: &Command, mut rest: &str) -> Result<prost_types::Struct, String> {
+    let mut fields = BTreeMap::new();
+    for arg in &command.arguments {
+        rest = rest.trim_start();
+        if rest.is_empty() {
+            if arg.optional {
+                break;
+            }
+            return Err(format!("missing argument `{}`", arg.name));
+        }
+
+        let value = if arg.r#type() == ArgumentType::Rest {
+            std::mem::take(&mut rest).trim_end()
+        } else {
+            let (value, tail) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+            rest = tail;
+            value
+        };
+
+        let kind = match arg.r#type() {
+            ArgumentType::Unspecified | ArgumentType::String | ArgumentType::Rest => {
+                Kind::StringValue(value.to_string())
+            }
+            ArgumentType::Number => Kind::NumberValue(
+                value
+                    .parse::<f64>()
+                    .map_err(|_| format!("`{}` must be a number", arg.name))?,
+            ),
+            ArgumentType::Integer => Kind::NumberValue(
+                value
+                    .parse::<i64>()
+                    .map_err(|_| format!("`{}` must be an integer", arg.name))?
+                    as f64,
+            ),
+            ArgumentType::Boolean => Kind::BoolValue(match value.to_lowercase().as_str() {
+                "true" | "yes" | "on" => true,
+                "false" | "no" | "off" => false,
+                _ => return Err(format!("`{}` must be either yes or no", arg.name)),
+            }),
+        };
+        fields.insert(arg.name.clone(), prost_types::Value { kind: Some(kind) });
+    }
+
+    if !rest.trim().is_empty() {
+        return Err(String::from("too many arguments"));
+    }
+
+    Ok(prost_types::Struct { fields })
+}
+
+/// The usage of a command, e.g. `-atis <airbase> [runway]`.
+fn usage(command: &Command) -> String {
+    let mut usage = format!(
+        "{}{}",
+        command.prefix.as_deref().unwrap_or("-"),
+        command.name
+    );
+    for arg in &command.arguments {
+        let name = if arg.r#type() == ArgumentType::Rest {
+            format!("{}...", arg.name)
+        } else {
+            arg.name.clone()
+        };
+        if arg.optional {
+            usage += &format!(" [{name}]");
+        } else {
+            usage += &format!(" <{name}>");
+        }
+    }
+    usage
+}
+
+#[cfg(test)]
+mod tests {
+    use stubs::chat::v0::Argument;
+
+    use super::*;
+    use crate::shutdown::Shutdown;
+
+    fn chat_commands(
+        commands: Vec<Command>,
+    ) -> (
+        ChatCommands,
+        mpsc::Receiver<Result<ListenResponse, Status>>,
+        Shutdown,
+    ) {
+        let shutdown = Shutdown::new();
+        let chat_commands = ChatCommands::new(shutdown.handle());
+        let (tx, rx) = mpsc::channel(8);
+        chat_commands
+            .0
+            .listeners
+            .lock()
+            .unwrap()
+            .insert(1, Listener { commands, tx });
+        (chat_commands, rx, shutdown)
+    }
+
+    fn atis(suppress: bool) -> Command {
+        Command {
+            name: "atis".to_string(),
+            prefix: None,
+            arguments: vec![Argument {
+                name: "airbase".to_string(),
+                r#type: ArgumentType::String.into(),
+                optional: false,
+            }],
+            suppress,
+            ..Default::default()
+        }
+    }
+
+    fn message(message: &str) -> ChatMessage {
+        ChatMessage {
+            player_id: 2,
+            player_name: "Hawk".to_string(),
+            ucid: "abc".to_string(),
+            coalition: 3,
+            message: message.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_dispatch_without_match() {
+        let (chat_commands, mut rx, _shutdown) = chat_commands(vec![atis(true)]);
+        assert!(chat_commands.dispatch(message("hello -atis")).is_none());
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_dispatch_invocation() {
+        let (chat_commands, mut rx, _shutdown) = chat_commands(vec![atis(true)]);
+        let result = chat_commands.dispatch(message("-ATIS Batumi")).unwrap();
+        assert!(result.suppress);
+        assert!(result.reply.is_none());
+
+        let invocation = rx.try_recv().unwrap().unwrap();
+        assert_eq!(invocation.command, "atis");
+        assert_eq!(invocation.player_id, 2);
+        assert_eq!(
+            invocation.arguments.unwrap().fields["airbase"].kind,
+            Some(Kind::StringValue("Batumi".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_dispatch_invalid_arguments_replies_usage() {
+        let (chat_commands, mut rx, _shutdown) = chat_commands(vec![atis(false)]);
+        let result = chat_commands.dispatch(message("-atis")).unwrap();
+        assert_eq!(
+            result.reply.as_deref(),
+            Some("missing argument `airbase` (usage: -atis <airbase>)")
+        );
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(validate(&[atis(false)]).is_ok());
+
+        let mut command = atis(false);
+        command.name = "at is".to_string();
+        assert!(validate(&[command]).is_err());
+
+        let mut command = atis(false);
+        command.arguments[0].optional = true;
+        command.arguments.push(Argument {
+            name: "runway".to_string(),
+            r#type: ArgumentType::String.into(),
+            optional: false,
+        });
+        assert!(validate(&[command]).is_err());
+    }
+
+    #[test]
+    fn test_result_without_reply_omits_reply() {
+        let result = ChatCommandResult {
+            suppress: true,
+            reply: None,
+        };
+        assert_eq!(
+            serde_json::to_value(&result).unwrap(),
+            serde_json::json!({ "suppress": true })
+        );
+    }
+}
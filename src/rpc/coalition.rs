@@ -1,8 +1,14 @@
+use std::collections::HashSet;
+
+use stubs::coalition::v0::add_group_request::point::PointType;
+use stubs::coalition::v0::add_group_request::{AircraftUnitTemplate, Point, Template};
 use stubs::coalition::v0::coalition_service_server::CoalitionService;
+use stubs::common::v0::{GroupCategory, Task};
 use stubs::*;
 use tonic::{Request, Response, Status};
 
 use super::MissionRpc;
+use super::controller::validate_task;
 
 #[tonic::async_trait]
 impl CoalitionService for MissionRpc {
@@ -10,6 +16,7 @@ impl CoalitionService for MissionRpc {
         &self,
         request: Request<coalition::v0::AddGroupRequest>,
     ) -> Result<Response<coalition::v0::AddGroupResponse>, Status> {
+        validate_group(request.get_ref()).map_err(Status::invalid_argument)?;
         let res = self.request("addGroup", request).await?;
         Ok(Response::new(res))
     }
@@ -62,3 +69,427 @@ impl CoalitionService for MissionRpc {
         Ok(Response::new(res))
     }
 }
+
+/// Waypoint actions supported by DCS for aircraft.
+const AIRCRAFT_ACTIONS: &[&str] = &[
+    "Turning Point",
+    "Fly Over Point",
+    "From Runway",
+    "From Parking Area",
+    "From Parking Area Hot",
+    "From Ground Area",
+    "From Ground Area Hot",
+    "Landing",
+    "LandingReFuAr",
+];
+
+/// Waypoint actions (formations) supported by DCS for ground units.
+const GROUND_ACTIONS: &[&str] = &[
+    "Off Road",
+    "On Road",
+    "On Railroads",
+    "Rank",
+    "Cone",
+    "Vee",
+    "Diamond",
+    "EchelonL",
+    "EchelonR",
+    "Custom",
+];
+
+/// Waypoint actions supported by DCS for ships.
+const SHIP_ACTIONS: &[&str] = &["Turning Point"];
+
+fn validate_group(request: &coalition::v0::AddGroupRequest) -> Result<(), String> {
+    if request.country == 0 {
+        return Err("country must be specified".to_string());
+    }
+
+    let category = request.group_category();
+    let template = request
+        .template
+        .as_ref()
+        .ok_or_else(|| "template must be specified".to_string())?;
+    match (category, template) {
+        (GroupCategory::Ground, Template::GroundTemplate(group)) => {
+            validate_name("group", &group.name)?;
+            if group.position.is_none() {
+                return Err("group position must be specified".to_string());
+            }
+            validate_units(group.units.iter().map(|u| (&u.name, &u.r#type)))?;
+            if group.units.iter().any(|u| u.position.is_none()) {
+                return Err("unit position must be specified".to_string());
+            }
+            validate_tasks(&group.tasks)?;
+            validate_route(&group.waypoints, GROUND_ACTIONS)
+        }
+        (GroupCategory::Ship, Template::ShipTemplate(group)) => {
+            validate_name("group", &group.name)?;
+            if group.position.is_none() {
+                return Err("group position must be specified".to_string());
+            }
+            validate_units(group.units.iter().map(|u| (&u.name, &u.r#type)))?;
+            for unit in &group.units {
+                if unit.position.is_none() {
+                    return Err("unit position must be specified".to_string());
+                }
+                validate_frequency(unit.frequency)?;
+            }
+            validate_route(&group.waypoints, SHIP_ACTIONS)
+        }
+        (GroupCategory::Helicopter, Template::HelicopterTemplate(group)) => {
+            validate_name("group", &group.name)?;
+            validate_frequency(group.frequency)?;
+            validate_aircraft(&group.units)?;
+            validate_aircraft_route(&group.waypoints)
+        }
+        (GroupCategory::Airplane, Template::PlaneTemplate(group)) => {
+            validate_name("group", &group.name)?;
+            validate_frequency(group.frequency)?;
+            validate_aircraft(&group.units)?;
+            validate_aircraft_route(&group.waypoints)
+        }
+        (GroupCategory::Unspecified, _) => Err("group category must be specified".to_string()),
+        (GroupCategory::Train, _) => Err("adding train groups is not supported".to_string()),
+        (category, _) => Err(format!(
+            "template does not match the group category `{}`",
+            category.as_str_name()
+        )),
+    }
+}
+
+fn validate_name(kind: &str, name: &str) -> Result<(), String> {
+    if name.trim().is_empty() {
+        return Err(format!("{kind} name must not be empty"));
+    }
+
+    Ok(())
+}
+
+fn validate_units<'a>(units: impl Iterator<Item = (&'a String, &'a String)>) -> Result<(), String> {
+    let mut names = HashSet::new();
+    for (name, r#type) in units {
+        validate_name("unit", name)?;
+        if r#type.is_empty() {
+            return Err(format!("type of unit `{name}` must be specified"));
+        }
+        if !names.insert(name) {
+            return Err(format!("unit name `{name}` is not unique"));
+        }
+    }
+
+    if names.is_empty() {
+        return Err("group must contain at least one unit".to_string());
+    }
+
+    Ok(())
+}
+
+fn validate_aircraft(units: &[AircraftUnitTemplate]) -> Result<(), String> {
+    validate_units(units.iter().map(|u| (&u.name, &u.r#type)))?;
+
+    for unit in units {
+        let Some(payload) = &unit.payload else {
+            continue;
+        };
+        if payload.fuel < 0.0 || payload.fuel.is_nan() {
+            return Err(format!("fuel of unit `{}` must not be negative", unit.name));
+        }
+        if payload.gun.is_some_and(|gun| gun > 100) {
+            return Err(format!(
+                "gun ammunition of unit `{}` must not exceed 100%",
+                unit.name
+            ));
+        }
+
+        let mut pylons = HashSet::new();
+        for pylon in &payload.pylons {
+            if pylon.number == 0 {
+                return Err(format!(
+                    "pylon numbers of unit `{}` must start at 1",
+                    unit.name
+                ));
+            }
+            if pylon.clsid.is_empty() {
+                return Err(format!(
+                    "clsid of pylon {} of unit `{}` must be specified",
+                    pylon.number, unit.name
+                ));
+            }
+            if !pylons.insert(pylon.number) {
+                return Err(format!(
+                    "pylon {} of unit `{}` is specified more than once",
+                    pylon.number, unit.name
+                ));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn validate_aircraft_route(waypoints: &[Point]) -> Result<(), String> {
+    let Some(first) = waypoints.first() else {
+        return Err("aircraft groups must have at least one waypoint".to_string());
+    };
+
+    if first.r#type() == PointType::Land {
+        return Err("the first waypoint must not be a landing waypoint".to_string());
+    }
+
+    for (i, point) in waypoints.iter().enumerate() {
+        let takeoff = matches!(
+            point.r#type(),
+            PointType::Takeoff | PointType::TakeoffParking | PointType::TakeoffParkingHot
+        );
+        if takeoff && i > 0 {
+            return Err(format!(
+                "only the first waypoint can be a takeoff waypoint (waypoint {})",
+                i + 1
+            ));
+        }
+        if (takeoff || point.r#type() == PointType::Land)
+            && point.airdrome_id.is_none()
+            && point.link_unit_id.is_none()
+        {
+            return Err(format!(
+                "waypoint {} requires an airdrome_id or link_unit_id",
+                i + 1
+            ));
+        }
+    }
+
+    validate_route(waypoints, AIRCRAFT_ACTIONS)
+}
+
+fn validate_route(waypoints: &[Point], actions: &[&str]) -> Result<(), String> {
+    for (i, point) in waypoints.iter().enumerate() {
+        if point.position.is_none() {
+            return Err(format!("position of waypoint {} must be specified", i + 1));
+        }
+        if point.speed < 0.0 || point.speed.is_nan() {
+            return Err(format!("speed of waypoint {} must not be negative", i + 1));
+        }
+        if !point.action.is_empty() && !actions.contains(&point.action.as_str()) {
+            return Err(format!(
+                "invalid action `{}` of waypoint {} (expected one of: {})",
+                point.action,
+                i + 1,
+                actions.join(", ")
+            ));
+        }
+        validate_tasks(&point.tasks)?;
+    }
+
+    Ok(())
+}
+
+fn validate_tasks(tasks: &[Task]) -> Result<(), String> {
+    tasks.iter().try_for_each(validate_task)
+}
+
+fn validate_frequency(frequency: Option<u64>) -> Result<(), String> {
+    if frequency == Some(0) {
+        return Err("frequency must be greater than 0".to_string());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use stubs::coalition::v0::AddGroupRequest;
+    use stubs::coalition::v0::add_group_request::{
+        GroundGroupTemplate, GroundUnitTemplate, Payload, PlaneGroupTemplate, Pylon,
+    };
+    use stubs::common::v0::InputPosition;
+
+    use super::*;
+
+    fn position() -> Option<InputPosition> {
+        Some(InputPosition {
+            lat: 42.0,
+            lon: 41.0,
+            alt: 1000.0,
+        })
+    }
+
+    fn waypoint(r#type: PointType) -> Point {
+        let mut point = Point {
+            position: position(),
+            speed: 150.0,
+            ..Default::default()
+        };
+        point.set_type(r#type);
+        point
+    }
+
+    fn aircraft(name: &str) -> AircraftUnitTemplate {
+        AircraftUnitTemplate {
+            name: name.to_string(),
+            r#type: "F-16C_50".to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn plane_group(units: Vec<AircraftUnitTemplate>, waypoints: Vec<Point>) -> AddGroupRequest {
+        let mut request = AddGroupRequest {
+            country: 2,
+            template: Some(Template::PlaneTemplate(PlaneGroupTemplate {
+                name: "Viper".to_string(),
+                units,
+                waypoints,
+                ..Default::default()
+            })),
+            ..Default::default()
+        };
+        request.set_group_category(GroupCategory::Airplane);
+        request
+    }
+
+    fn ground_group(units: &[&str]) -> AddGroupRequest {
+        let mut request = AddGroupRequest {
+            country: 2,
+            template: Some(Template::GroundTemplate(GroundGroupTemplate {
+                name: "Armor".to_string(),
+                position: position(),
+                units: units
+                    .iter()
+                    .map(|name| GroundUnitTemplate {
+                        name: name.to_string(),
+                        r#type: "M-1 Abrams".to_string(),
+                        position: position(),
+                        ..Default::default()
+                    })
+                    .collect(),
+                ..Default::default()
+            })),
+            ..Default::default()
+        };
+        request.set_group_category(GroupCategory::Ground);
+        request
+    }
+
+    fn with_pylons(numbers: &[u32]) -> AircraftUnitTemplate {
+        AircraftUnitTemplate {
+            payload: Some(Payload {
+                fuel: 3249.0,
+                pylons: numbers
+                    .iter()
+                    .map(|number| Pylon {
+                        number: *number,
+                        clsid: "{6CEB49FC-DED8-4DED-B053-E1F033FF72D3}".to_string(),
+                    })
+                    .collect(),
+                ..Default::default()
+            }),
+            ..aircraft("Viper 1-1")
+        }
+    }
+
+    fn takeoff_from(airdrome_id: u32) -> Point {
+        Point {
+            airdrome_id: Some(airdrome_id),
+            ..waypoint(PointType::TakeoffParking)
+        }
+    }
+
+    #[test]
+    fn test_valid_groups() {
+        validate_group(&ground_group(&["Tank 1", "Tank 2"])).unwrap();
+        validate_group(&plane_group(
+            vec![with_pylons(&[1, 2, 9])],
+            vec![
+                takeoff_from(22),
+                waypoint(PointType::TurningPoint),
+                Point {
+                    airdrome_id: Some(22),
+                    ..waypoint(PointType::Land)
+                },
+            ],
+        ))
+        .unwrap();
+    }
+
+    #[test]
+    fn test_mismatched_category_and_template() {
+        let mut request = ground_group(&["Tank 1"]);
+        request.set_group_category(GroupCategory::Airplane);
+        let err = validate_group(&request).unwrap_err();
+        assert!(err.contains("does not match"), "{err}");
+
+        let mut request = plane_group(
+            vec![aircraft("Viper 1-1")],
+            vec![waypoint(PointType::TurningPoint)],
+        );
+        request.set_group_category(GroupCategory::Helicopter);
+        assert!(validate_group(&request).is_err());
+
+        request.set_group_category(GroupCategory::Unspecified);
+        assert!(validate_group(&request).is_err());
+    }
+
+    #[test]
+    fn test_duplicate_unit_names() {
+        let err = validate_group(&ground_group(&["Tank 1", "Tank 1"])).unwrap_err();
+        assert!(err.contains("not unique"), "{err}");
+
+        let err = validate_group(&plane_group(
+            vec![aircraft("Viper 1-1"), aircraft("Viper 1-1")],
+            vec![waypoint(PointType::TurningPoint)],
+        ))
+        .unwrap_err();
+        assert!(err.contains("not unique"), "{err}");
+    }
+
+    #[test]
+    fn test_invalid_pylons() {
+        let route = || vec![waypoint(PointType::TurningPoint)];
+
+        let err = validate_group(&plane_group(vec![with_pylons(&[0, 1])], route())).unwrap_err();
+        assert!(err.contains("must start at 1"), "{err}");
+
+        let err = validate_group(&plane_group(vec![with_pylons(&[1, 2, 1])], route())).unwrap_err();
+        assert!(err.contains("more than once"), "{err}");
+    }
+
+    #[test]
+    fn test_takeoff_not_on_first_waypoint() {
+        let err = validate_group(&plane_group(
+            vec![aircraft("Viper 1-1")],
+            vec![waypoint(PointType::TurningPoint), takeoff_from(22)],
+        ))
+        .unwrap_err();
+        assert!(err.contains("only the first waypoint"), "{err}");
+    }
+
+    #[test]
+    fn test_takeoff_and_landing_without_airdrome() {
+        let err = validate_group(&plane_group(
+            vec![aircraft("Viper 1-1")],
+            vec![waypoint(PointType::Takeoff)],
+        ))
+        .unwrap_err();
+        assert!(err.contains("waypoint 1 requires an airdrome_id"), "{err}");
+
+        let err = validate_group(&plane_group(
+            vec![aircraft("Viper 1-1")],
+            vec![waypoint(PointType::TurningPoint), waypoint(PointType::Land)],
+        ))
+        .unwrap_err();
+        assert!(err.contains("waypoint 2 requires an airdrome_id"), "{err}");
+
+        // landing on a carrier
+        validate_group(&plane_group(
+            vec![aircraft("Viper 1-1")],
+            vec![
+                waypoint(PointType::TurningPoint),
+                Point {
+                    link_unit_id: Some(1),
+                    ..waypoint(PointType::Land)
+                },
+            ],
+        ))
+        .unwrap();
+    }
+}
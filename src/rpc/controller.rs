@@ -1,5 +1,8 @@
+use stubs::common::v0::orbit_task::Pattern;
+use stubs::common::v0::{Task, task};
 use stubs::controller;
 use stubs::controller::v0::controller_service_server::ControllerService;
+use stubs::controller::v0::{command, option};
 use tonic::{Request, Response, Status};
 
 use super::MissionRpc;
@@ -20,4 +23,300 @@ impl ControllerService for MissionRpc {
         let res = self.request("getDetectedTargets", request).await?;
         Ok(Response::new(res))
     }
+
+    async fn set_task(
+        &self,
+        request: Request<controller::v0::SetTaskRequest>,
+    ) -> Result<Response<controller::v0::SetTaskResponse>, Status> {
+        validate_required_task(request.get_ref().task.as_ref())
+            .map_err(Status::invalid_argument)?;
+        let res = self.request("setTask", request).await?;
+        Ok(Response::new(res))
+    }
+
+    async fn push_task(
+        &self,
+        request: Request<controller::v0::PushTaskRequest>,
+    ) -> Result<Response<controller::v0::PushTaskResponse>, Status> {
+        validate_required_task(request.get_ref().task.as_ref())
+            .map_err(Status::invalid_argument)?;
+        let res = self.request("pushTask", request).await?;
+        Ok(Response::new(res))
+    }
+
+    async fn pop_task(
+        &self,
+        request: Request<controller::v0::PopTaskRequest>,
+    ) -> Result<Response<controller::v0::PopTaskResponse>, Status> {
+        let res = self.request("popTask", request).await?;
+        Ok(Response::new(res))
+    }
+
+    async fn reset_task(
+        &self,
+        request: Request<controller::v0::ResetTaskRequest>,
+    ) -> Result<Response<controller::v0::ResetTaskResponse>, Status> {
+        let res = self.request("resetTask", request).await?;
+        Ok(Response::new(res))
+    }
+
+    async fn has_task(
+        &self,
+        request: Request<controller::v0::HasTaskRequest>,
+    ) -> Result<Response<controller::v0::HasTaskResponse>, Status> {
+        let res = self.request("hasTask", request).await?;
+        Ok(Response::new(res))
+    }
+
+    async fn set_option(
+        &self,
+        request: Request<controller::v0::SetOptionRequest>,
+    ) -> Result<Response<controller::v0::SetOptionResponse>, Status> {
+        let unspecified = match request.get_ref().option.as_ref().and_then(|o| o.option) {
+            None => true,
+            Some(option::Option::Roe(roe)) => roe == option::Roe::Unspecified as i32,
+            Some(option::Option::ReactionOnThreat(reaction)) => {
+                reaction == option::ReactionOnThreat::Unspecified as i32
+            }
+        };
+        if unspecified {
+            return Err(Status::invalid_argument("option must be specified"));
+        }
+
+        let res = self.request("setOption", request).await?;
+        Ok(Response::new(res))
+    }
+
+    async fn set_command(
+        &self,
+        request: Request<controller::v0::SetCommandRequest>,
+    ) -> Result<Response<controller::v0::SetCommandResponse>, Status> {
+        match request.get_ref().command.as_ref().and_then(|c| c.command) {
+            None => return Err(Status::invalid_argument("command must be specified")),
+            Some(command::Command::SetFrequency(command)) => {
+                if command.frequency == 0 {
+                    return Err(Status::invalid_argument("frequency must be greater than 0"));
+                }
+                if command
+                    .power
+                    .is_some_and(|power| power <= 0.0 || power.is_nan())
+                {
+                    return Err(Status::invalid_argument("power must be greater than 0"));
+                }
+            }
+            Some(_) => {}
+        }
+
+        let res = self.request("setCommand", request).await?;
+        Ok(Response::new(res))
+    }
+
+    async fn set_on_off(
+        &self,
+        request: Request<controller::v0::SetOnOffRequest>,
+    ) -> Result<Response<controller::v0::SetOnOffResponse>, Status> {
+        let res = self.request("setOnOff", request).await?;
+        Ok(Response::new(res))
+    }
+}
+
+fn validate_required_task(task: Option<&Task>) -> Result<(), String> {
+    validate_task(task.ok_or_else(|| "task must be specified".to_string())?)
+}
+
+/// Validate a task before it is converted into a DCS task table by the mission.
+pub(super) fn validate_task(task: &Task) -> Result<(), String> {
+    match &task.task {
+        None => return Err("task must be specified".to_string()),
+        Some(task::Task::Orbit(orbit)) => {
+            if orbit.pattern() == Pattern::RaceTrack && orbit.position2.is_none() {
+                return Err("race track orbits require a position2".to_string());
+            }
+            if orbit
+                .speed
+                .is_some_and(|speed| speed < 0.0 || speed.is_nan())
+            {
+                return Err("orbit speed must not be negative".to_string());
+            }
+        }
+        Some(task::Task::Land(land)) => {
+            if land.position.is_none() {
+                return Err("land position must be specified".to_string());
+            }
+            if land.duration.is_some_and(|d| d < 0.0 || d.is_nan()) {
+                return Err("land duration must not be negative".to_string());
+            }
+        }
+        Some(task::Task::Refueling(_)) => {}
+        Some(task::Task::AttackGroup(attack)) => {
+            if attack.group_name.is_empty() {
+                return Err("group name of the attack task must be specified".to_string());
+            }
+        }
+        Some(task::Task::AttackUnit(attack)) => {
+            if attack.unit_name.is_empty() {
+                return Err("unit name of the attack task must be specified".to_string());
+            }
+        }
+        Some(task::Task::Bombing(bombing)) => {
+            if bombing.position.is_none() {
+                return Err("bombing position must be specified".to_string());
+            }
+        }
+        Some(task::Task::Follow(follow)) => {
+            if follow.group_name.is_empty() {
+                return Err("group name of the follow task must be specified".to_string());
+            }
+            if follow.last_waypoint == Some(0) {
+                return Err("waypoints start at 1".to_string());
+            }
+        }
+        Some(task::Task::Escort(escort)) => {
+            if escort.group_name.is_empty() {
+                return Err("group name of the escort task must be specified".to_string());
+            }
+            if escort.last_waypoint == Some(0) {
+                return Err("waypoints start at 1".to_string());
+            }
+            if escort.engagement_distance < 0.0 || escort.engagement_distance.is_nan() {
+                return Err("engagement distance must not be negative".to_string());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use stubs::common::v0::{
+        AttackGroupTask, AttackUnitTask, BombingTask, EscortTask, FollowTask, InputPosition,
+        LandTask, OrbitTask, RefuelingTask,
+    };
+
+    use super::*;
+
+    fn position() -> Option<InputPosition> {
+        Some(InputPosition {
+            lat: 42.0,
+            lon: 41.0,
+            alt: 0.0,
+        })
+    }
+
+    fn validate(task: task::Task) -> Result<(), String> {
+        validate_task(&Task { task: Some(task) })
+    }
+
+    #[test]
+    fn test_missing_task() {
+        assert!(validate_task(&Task { task: None }).is_err());
+        assert!(validate_required_task(None).is_err());
+        validate_required_task(Some(&Task {
+            task: Some(task::Task::Refueling(RefuelingTask {})),
+        }))
+        .unwrap();
+    }
+
+    #[test]
+    fn test_orbit() {
+        validate(task::Task::Orbit(OrbitTask::default())).unwrap();
+
+        let mut race_track = OrbitTask {
+            position: position(),
+            ..Default::default()
+        };
+        race_track.set_pattern(Pattern::RaceTrack);
+        let err = validate(task::Task::Orbit(race_track)).unwrap_err();
+        assert!(err.contains("position2"), "{err}");
+        race_track.position2 = position();
+        validate(task::Task::Orbit(race_track)).unwrap();
+
+        let err = validate(task::Task::Orbit(OrbitTask {
+            speed: Some(-1.0),
+            ..Default::default()
+        }))
+        .unwrap_err();
+        assert!(err.contains("speed"), "{err}");
+    }
+
+    #[test]
+    fn test_land() {
+        validate(task::Task::Land(LandTask {
+            position: position(),
+            duration: Some(60.0),
+        }))
+        .unwrap();
+
+        let err = validate(task::Task::Land(LandTask {
+            position: None,
+            duration: None,
+        }))
+        .unwrap_err();
+        assert!(err.contains("position"), "{err}");
+
+        let err = validate(task::Task::Land(LandTask {
+            position: position(),
+            duration: Some(f64::NAN),
+        }))
+        .unwrap_err();
+        assert!(err.contains("duration"), "{err}");
+    }
+
+    #[test]
+    fn test_attack_and_bombing() {
+        validate(task::Task::AttackGroup(AttackGroupTask {
+            group_name: "Armor".to_string(),
+            ..Default::default()
+        }))
+        .unwrap();
+        assert!(validate(task::Task::AttackGroup(AttackGroupTask::default())).is_err());
+
+        validate(task::Task::AttackUnit(AttackUnitTask {
+            unit_name: "Tank 1".to_string(),
+            ..Default::default()
+        }))
+        .unwrap();
+        assert!(validate(task::Task::AttackUnit(AttackUnitTask::default())).is_err());
+
+        validate(task::Task::Bombing(BombingTask {
+            position: position(),
+            ..Default::default()
+        }))
+        .unwrap();
+        assert!(validate(task::Task::Bombing(BombingTask::default())).is_err());
+    }
+
+    #[test]
+    fn test_follow_and_escort() {
+        validate(task::Task::Follow(FollowTask {
+            group_name: "Tanker".to_string(),
+            last_waypoint: Some(1),
+            ..Default::default()
+        }))
+        .unwrap();
+        assert!(validate(task::Task::Follow(FollowTask::default())).is_err());
+        let err = validate(task::Task::Follow(FollowTask {
+            group_name: "Tanker".to_string(),
+            last_waypoint: Some(0),
+            ..Default::default()
+        }))
+        .unwrap_err();
+        assert!(err.contains("start at 1"), "{err}");
+
+        validate(task::Task::Escort(EscortTask {
+            group_name: "Strike".to_string(),
+            engagement_distance: 60_000.0,
+            ..Default::default()
+        }))
+        .unwrap();
+        assert!(validate(task::Task::Escort(EscortTask::default())).is_err());
+        let err = validate(task::Task::Escort(EscortTask {
+            group_name: "Strike".to_string(),
+            engagement_distance: -1.0,
+            ..Default::default()
+        }))
+        .unwrap_err();
+        assert!(err.contains("engagement distance"), "{err}");
+    }
 }
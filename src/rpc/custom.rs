@@ -62,14 +62,15 @@ impl CustomService for MissionRpc {
         // fine to just use the scenario's start time, especially since it is cached and thus
         // prevents unnecessary roundtrips to the MSE.
         let date = self.get_scenario_start_time().await?.date();
-        let declination = igrf::declination(position.lat, position.lon, position.alt as u32, date)
-            .map(|f| f.d)
-            .or_else(|err| match err {
-                igrf::Error::DateOutOfRange(f) => Ok(f.d),
-                err => Err(Status::internal(format!(
-                    "failed to estimate magnetic declination: {err}"
-                ))),
-            })?;
+        let declination =
+            match igrf::declination(position.lat, position.lon, position.alt as u32, date) {
+                Ok(f) | Err(igrf::Error::DateOutOfRange(f)) => f.d,
+                Err(err) => {
+                    return Err(Status::internal(format!(
+                        "failed to estimate magnetic declination: {err}"
+                    )));
+                }
+            };
 
         // reduce precision to two decimal places
         let declination = ((declination * 100.0).round() / 100.0).neg();
@@ -78,4 +79,20 @@ impl CustomService for MissionRpc {
             declination,
         }))
     }
+
+    async fn call(
+        &self,
+        request: Request<custom::v0::CallRequest>,
+    ) -> Result<Response<custom::v0::CallResponse>, Status> {
+        let res = self.request("callCustomMethod", request).await?;
+        Ok(Response::new(res))
+    }
+
+    async fn list_methods(
+        &self,
+        request: Request<custom::v0::ListMethodsRequest>,
+    ) -> Result<Response<custom::v0::ListMethodsResponse>, Status> {
+        let res = self.request("listCustomMethods", request).await?;
+        Ok(Response::new(res))
+    }
 }
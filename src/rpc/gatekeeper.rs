@@ -0,0 +1,314 @@
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use futures_util::stream::FuturesUnordered;
+use futures_util::{Stream, StreamExt};
+use stubs::gatekeeper::v0::gatekeeper_service_server::GatekeeperService;
+use stubs::gatekeeper::v0::{DecideRequest, DecideResponse, decide_response};
+use tokio::sync::{mpsc, oneshot};
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::{Request, Response, Status, Streaming};
+
+use crate::config::{GatekeeperConfig, GatekeeperVerdict};
+use crate::shutdown::{AbortableStream, ShutdownHandle};
+
+/// Forwards decisions about players trying to connect or to change into a slot to all connected
+/// gatekeeper clients and collects their verdicts.
+#[derive(Clone)]
+pub struct Gatekeeper(Arc<Inner>);
+
+struct Inner {
+    config: GatekeeperConfig,
+    shutdown_signal: ShutdownHandle,
+    /// The id given to the next decision or gatekeeper.
+    next_id: AtomicU64,
+    /// All currently connected gatekeepers (by gatekeeper id).
+    gatekeepers: Mutex<HashMap<u64, mpsc::Sender<Result<DecideResponse, Status>>>>,
+    /// All decisions waiting for a verdict (by decision id and gatekeeper id).
+    pending: Mutex<HashMap<(u64, u64), oneshot::Sender<Verdict>>>,
+}
+
+/// The verdict returned to Lua.
+#[derive(Debug, serde::Serialize)]
+pub struct Verdict {
+    pub allow: bool,
+    // skipped instead of serialized as `null`, which would be truthy in Lua
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+impl Gatekeeper {
+    pub fn new(config: GatekeeperConfig, shutdown_signal: ShutdownHandle) -> Self {
+        Self(Arc::new(Inner {
+            config,
+            shutdown_signal,
+            next_id: AtomicU64::new(1),
+            gatekeepers: Default::default(),
+            pending: Default::default(),
+        }))
+    }
+
+    /// Ask all connected gatekeepers for a verdict. Returns `None` if no gatekeeper is connected.
+    pub async fn decide(&self, decision: decide_response::Decision) -> Option<Verdict> {
+        let id = self.0.next_id.fetch_add(1, Ordering::Relaxed);
+        let mut verdicts = FuturesUnordered::new();
+        {
+            let gatekeepers = self.0.gatekeepers.lock().unwrap();
+            let mut pending = self.0.pending.lock().unwrap();
+            for (gatekeeper_id, tx) in gatekeepers.iter() {
+                let req = DecideResponse {
+                    id,
+                    decision: Some(decision.clone()),
+                };
+                if tx.try_send(Ok(req)).is_ok() {
+                    let (verdict_tx, verdict_rx) = oneshot::channel();
+                    pending.insert((id, *gatekeeper_id), verdict_tx);
+                    verdicts.push(verdict_rx);
+                } else {
+                    log::warn!(
+                        "Gatekeeper {} is not keeping up, skipping it",
+                        gatekeeper_id
+                    );
+                }
+            }
+        }
+        if verdicts.is_empty() {
+            return None;
+        }
+
+        let timeout = tokio::time::sleep(Duration::from_millis(self.0.config.timeout));
+        let mut timeout = std::pin::pin!(timeout);
+        let verdict = loop {
+            // wait for either the timeout or the next verdict, whatever happens first
+            tokio::select! {
+                _ = &mut timeout => {
+                    log::warn!(
+                        "Gatekeeper didn't answer decision {} within {}ms, using default verdict",
+                        id,
+                        self.0.config.timeout
+                    );
+                    break Verdict {
+                        allow: self.0.config.default_verdict == GatekeeperVerdict::Allow,
+                        reason: None,
+                    };
+                }
+                verdict = verdicts.next() => match verdict {
+                    // the first deny wins
+                    Some(Ok(verdict)) if !verdict.allow => break verdict,
+                    // allowed, or the gatekeeper disconnected in the meantime
+                    Some(_) => continue,
+                    None => break Verdict {
+                        allow: true,
+                        reason: None,
+                    },
+                }
+            }
+        };
+
+        self.0
+            .pending
+            .lock()
+            .unwrap()
+            .retain(|(decision_id, _), _| *decision_id != id);
+
+        Some(verdict)
+    }
+
+    fn connect(&self) -> (u64, mpsc::Receiver<Result<DecideResponse, Status>>) {
+        let (tx, rx) = mpsc::channel(64);
+        let gatekeeper_id = self.0.next_id.fetch_add(1, Ordering::Relaxed);
+        self.0.gatekeepers.lock().unwrap().insert(gatekeeper_id, tx);
+        log::info!("Gatekeeper {} connected", gatekeeper_id);
+        (gatekeeper_id, rx)
+    }
+
+    fn disconnect(&self, gatekeeper_id: u64) {
+        self.0.gatekeepers.lock().unwrap().remove(&gatekeeper_id);
+        // drop the gatekeeper's pending verdicts, so that open decisions don't wait for it until
+        // they time out
+        self.0
+            .pending
+            .lock()
+            .unwrap()
+            .retain(|(_, id), _| *id != gatekeeper_id);
+        log::info!("Gatekeeper {} disconnected", gatekeeper_id);
+    }
+
+    fn resolve(&self, gatekeeper_id: u64, verdict: DecideRequest) {
+        let tx = self
+            .0
+            .pending
+            .lock()
+            .unwrap()
+            .remove(&(verdict.id, gatekeeper_id));
+        match tx {
+            Some(tx) => {
+                // ignore error, as the decision might have timed out in the meantime
+                let _ = tx.send(Verdict {
+                    allow: verdict.allow,
+                    reason: verdict.reason,
+                });
+            }
+            None => log::debug!(
+                "Received verdict for unknown or timed out decision {}",
+                verdict.id
+            ),
+        }
+    }
+}
+
+#[tonic::async_trait]
+impl GatekeeperService for Gatekeeper {
+    type DecideStream =
+        Pin<Box<dyn Stream<Item = Result<DecideResponse, Status>> + Send + Sync + 'static>>;
+
+    async fn decide(
+        &self,
+        request: Request<Streaming<DecideRequest>>,
+    ) -> Result<Response<Self::DecideStream>, Status> {
+        let mut verdicts = request.into_inner();
+        let (gatekeeper_id, rx) = self.connect();
+
+        let gatekeeper = self.clone();
+        tokio::spawn(async move {
+            // read verdicts until the gatekeeper disconnects
+            while let Some(verdict) = verdicts.next().await {
+                match verdict {
+                    Ok(verdict) => gatekeeper.resolve(gatekeeper_id, verdict),
+                    Err(err) => {
+                        log::debug!("Gatekeeper {} stream failed: {}", gatekeeper_id, err);
+                        break;
+                    }
+                }
+            }
+
+            gatekeeper.disconnect(gatekeeper_id);
+        });
+
+        let stream = AbortableStream::new(self.0.shutdown_signal.signal(), ReceiverStream::new(rx));
+        Ok(Response::new(Box::pin(stream)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use stubs::gatekeeper::v0::decide_response::TryConnect;
+
+    use super::*;
+    use crate::shutdown::Shutdown;
+
+    fn gatekeeper(timeout: u64, default_verdict: GatekeeperVerdict) -> (Gatekeeper, Shutdown) {
+        let shutdown = Shutdown::new();
+        let gatekeeper = Gatekeeper::new(
+            GatekeeperConfig {
+                timeout,
+                default_verdict,
+            },
+            shutdown.handle(),
+        );
+        (gatekeeper, shutdown)
+    }
+
+    fn decision() -> decide_response::Decision {
+        decide_response::Decision::TryConnect(TryConnect {
+            addr: "127.0.0.1".to_string(),
+            name: "Hawk".to_string(),
+            ucid: "abc".to_string(),
+            player_id: 2,
+        })
+    }
+
+    async fn next_id(rx: &mut mpsc::Receiver<Result<DecideResponse, Status>>) -> u64 {
+        rx.recv().await.unwrap().unwrap().id
+    }
+
+    #[tokio::test]
+    async fn test_without_gatekeeper() {
+        let (gatekeeper, _shutdown) = gatekeeper(1000, GatekeeperVerdict::Deny);
+        assert!(gatekeeper.decide(decision()).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_first_deny_wins() {
+        let (gatekeeper, _shutdown) = gatekeeper(5000, GatekeeperVerdict::Allow);
+        let (a, mut rx_a) = gatekeeper.connect();
+        let (b, mut rx_b) = gatekeeper.connect();
+
+        let decide = tokio::spawn({
+            let gatekeeper = gatekeeper.clone();
+            async move { gatekeeper.decide(decision()).await }
+        });
+        let id = next_id(&mut rx_a).await;
+        assert_eq!(next_id(&mut rx_b).await, id);
+
+        gatekeeper.resolve(
+            a,
+            DecideRequest {
+                id,
+                allow: true,
+                reason: None,
+            },
+        );
+        gatekeeper.resolve(
+            b,
+            DecideRequest {
+                id,
+                allow: false,
+                reason: Some("Whitelist only".to_string()),
+            },
+        );
+
+        let verdict = decide.await.unwrap().unwrap();
+        assert!(!verdict.allow);
+        assert_eq!(verdict.reason.as_deref(), Some("Whitelist only"));
+        assert!(gatekeeper.0.pending.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_timeout_uses_default_verdict() {
+        let (gatekeeper, _shutdown) = gatekeeper(50, GatekeeperVerdict::Deny);
+        let (_, _rx) = gatekeeper.connect();
+
+        let verdict = gatekeeper.decide(decision()).await.unwrap();
+        assert!(!verdict.allow);
+        assert!(verdict.reason.is_none());
+        assert!(gatekeeper.0.pending.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_disconnect_does_not_wait_for_timeout() {
+        let (gatekeeper, _shutdown) = gatekeeper(60_000, GatekeeperVerdict::Deny);
+        let (id, mut rx) = gatekeeper.connect();
+
+        let start = Instant::now();
+        let decide = tokio::spawn({
+            let gatekeeper = gatekeeper.clone();
+            async move { gatekeeper.decide(decision()).await }
+        });
+        next_id(&mut rx).await;
+        gatekeeper.disconnect(id);
+
+        let verdict = decide.await.unwrap().unwrap();
+        assert!(verdict.allow);
+        assert!(start.elapsed() < Duration::from_secs(5));
+        assert!(gatekeeper.0.pending.lock().unwrap().is_empty());
+        assert!(gatekeeper.0.gatekeepers.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_verdict_without_reason_omits_reason() {
+        let verdict = Verdict {
+            allow: false,
+            reason: None,
+        };
+        assert_eq!(
+            serde_json::to_value(&verdict).unwrap(),
+            serde_json::json!({ "allow": false })
+        );
+    }
+}
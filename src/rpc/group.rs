@@ -29,4 +29,76 @@ impl GroupService for MissionRpc {
         let res = self.request("groupDestroy", request).await?;
         Ok(Response::new(res))
     }
+
+    async fn get(
+        &self,
+        request: Request<group::v0::GetRequest>,
+    ) -> Result<Response<group::v0::GetResponse>, Status> {
+        let res = self.request("groupGet", request).await?;
+        Ok(Response::new(res))
+    }
+
+    async fn get_size(
+        &self,
+        request: Request<group::v0::GetSizeRequest>,
+    ) -> Result<Response<group::v0::GetSizeResponse>, Status> {
+        let res = self.request("groupGetSize", request).await?;
+        Ok(Response::new(res))
+    }
+
+    async fn get_initial_size(
+        &self,
+        request: Request<group::v0::GetInitialSizeRequest>,
+    ) -> Result<Response<group::v0::GetInitialSizeResponse>, Status> {
+        let res = self.request("groupGetInitialSize", request).await?;
+        Ok(Response::new(res))
+    }
+
+    async fn get_category(
+        &self,
+        request: Request<group::v0::GetCategoryRequest>,
+    ) -> Result<Response<group::v0::GetCategoryResponse>, Status> {
+        let res = self.request("groupGetCategory", request).await?;
+        Ok(Response::new(res))
+    }
+
+    async fn get_controller(
+        &self,
+        request: Request<group::v0::GetControllerRequest>,
+    ) -> Result<Response<group::v0::GetControllerResponse>, Status> {
+        let res = self.request("groupGetController", request).await?;
+        Ok(Response::new(res))
+    }
+
+    async fn stop_moving(
+        &self,
+        request: Request<group::v0::StopMovingRequest>,
+    ) -> Result<Response<group::v0::StopMovingResponse>, Status> {
+        let res = self.request("groupStopMoving", request).await?;
+        Ok(Response::new(res))
+    }
+
+    async fn continue_moving(
+        &self,
+        request: Request<group::v0::ContinueMovingRequest>,
+    ) -> Result<Response<group::v0::ContinueMovingResponse>, Status> {
+        let res = self.request("groupContinueMoving", request).await?;
+        Ok(Response::new(res))
+    }
+
+    async fn set_ai_on_off(
+        &self,
+        request: Request<group::v0::SetAiOnOffRequest>,
+    ) -> Result<Response<group::v0::SetAiOnOffResponse>, Status> {
+        let res = self.request("groupSetAiOnOff", request).await?;
+        Ok(Response::new(res))
+    }
+
+    async fn deactivate(
+        &self,
+        request: Request<group::v0::DeactivateRequest>,
+    ) -> Result<Response<group::v0::DeactivateResponse>, Status> {
+        let res = self.request("groupDeactivate", request).await?;
+        Ok(Response::new(res))
+    }
 }
@@ -161,4 +161,76 @@ impl HookService for HookRpc {
         let res = self.request("getBallisticsCount", request).await?;
         Ok(Response::new(res))
     }
+
+    async fn get_model_time(
+        &self,
+        request: Request<hook::v0::GetModelTimeRequest>,
+    ) -> Result<Response<hook::v0::GetModelTimeResponse>, Status> {
+        let res = self.request("getModelTime", request).await?;
+        Ok(Response::new(res))
+    }
+
+    async fn get_player_stat(
+        &self,
+        request: Request<hook::v0::GetPlayerStatRequest>,
+    ) -> Result<Response<hook::v0::GetPlayerStatResponse>, Status> {
+        let res = self.request("getPlayerStat", request).await?;
+        Ok(Response::new(res))
+    }
+
+    async fn get_my_player_id(
+        &self,
+        request: Request<hook::v0::GetMyPlayerIdRequest>,
+    ) -> Result<Response<hook::v0::GetMyPlayerIdResponse>, Status> {
+        let res = self.request("getMyPlayerId", request).await?;
+        Ok(Response::new(res))
+    }
+
+    async fn get_server_id(
+        &self,
+        request: Request<hook::v0::GetServerIdRequest>,
+    ) -> Result<Response<hook::v0::GetServerIdResponse>, Status> {
+        let res = self.request("getServerId", request).await?;
+        Ok(Response::new(res))
+    }
+
+    async fn set_slot(
+        &self,
+        request: Request<hook::v0::SetSlotRequest>,
+    ) -> Result<Response<hook::v0::SetSlotResponse>, Status> {
+        let res = self.request("setSlot", request).await?;
+        Ok(Response::new(res))
+    }
+
+    async fn get_available_slots(
+        &self,
+        request: Request<hook::v0::GetAvailableSlotsRequest>,
+    ) -> Result<Response<hook::v0::GetAvailableSlotsResponse>, Status> {
+        let res = self.request("getAvailableSlots", request).await?;
+        Ok(Response::new(res))
+    }
+
+    async fn get_available_coalitions(
+        &self,
+        request: Request<hook::v0::GetAvailableCoalitionsRequest>,
+    ) -> Result<Response<hook::v0::GetAvailableCoalitionsResponse>, Status> {
+        let res = self.request("getAvailableCoalitions", request).await?;
+        Ok(Response::new(res))
+    }
+
+    async fn get_current_mission(
+        &self,
+        request: Request<hook::v0::GetCurrentMissionRequest>,
+    ) -> Result<Response<hook::v0::GetCurrentMissionResponse>, Status> {
+        let res = self.request("getCurrentMission", request).await?;
+        Ok(Response::new(res))
+    }
+
+    async fn get_mission_options(
+        &self,
+        request: Request<hook::v0::GetMissionOptionsRequest>,
+    ) -> Result<Response<hook::v0::GetMissionOptionsResponse>, Status> {
+        let res = self.request("getMissionOptions", request).await?;
+        Ok(Response::new(res))
+    }
 }
@@ -0,0 +1,278 @@
+use stubs::coalition::v0::add_group_request::Point;
+use stubs::coalition::v0::add_group_request::point::{AltitudeType, PointType};
+use stubs::common::v0::InputPosition;
+use stubs::land::v0::RoadType;
+use stubs::land::v0::land_service_server::LandService;
+use stubs::*;
+use tonic::{Request, Response, Status};
+
+use super::MissionRpc;
+
+#[tonic::async_trait]
+impl LandService for MissionRpc {
+    async fn get_height(
+        &self,
+        request: Request<land::v0::GetHeightRequest>,
+    ) -> Result<Response<land::v0::GetHeightResponse>, Status> {
+        let res = self.request("getHeight", request).await?;
+        Ok(Response::new(res))
+    }
+
+    async fn batch_get_height(
+        &self,
+        request: Request<land::v0::BatchGetHeightRequest>,
+    ) -> Result<Response<land::v0::BatchGetHeightResponse>, Status> {
+        let res = self.request("batchGetHeight", request).await?;
+        Ok(Response::new(res))
+    }
+
+    async fn get_surface_height_with_seabed(
+        &self,
+        request: Request<land::v0::GetSurfaceHeightWithSeabedRequest>,
+    ) -> Result<Response<land::v0::GetSurfaceHeightWithSeabedResponse>, Status> {
+        let res = self.request("getSurfaceHeightWithSeabed", request).await?;
+        Ok(Response::new(res))
+    }
+
+    async fn batch_get_surface_height_with_seabed(
+        &self,
+        request: Request<land::v0::BatchGetSurfaceHeightWithSeabedRequest>,
+    ) -> Result<Response<land::v0::BatchGetSurfaceHeightWithSeabedResponse>, Status> {
+        let res = self
+            .request("batchGetSurfaceHeightWithSeabed", request)
+            .await?;
+        Ok(Response::new(res))
+    }
+
+    async fn get_surface_type(
+        &self,
+        request: Request<land::v0::GetSurfaceTypeRequest>,
+    ) -> Result<Response<land::v0::GetSurfaceTypeResponse>, Status> {
+        let res = self.request("getSurfaceType", request).await?;
+        Ok(Response::new(res))
+    }
+
+    async fn batch_get_surface_type(
+        &self,
+        request: Request<land::v0::BatchGetSurfaceTypeRequest>,
+    ) -> Result<Response<land::v0::BatchGetSurfaceTypeResponse>, Status> {
+        let res = self.request("batchGetSurfaceType", request).await?;
+        Ok(Response::new(res))
+    }
+
+    async fn is_visible(
+        &self,
+        request: Request<land::v0::IsVisibleRequest>,
+    ) -> Result<Response<land::v0::IsVisibleResponse>, Status> {
+        let res = self.request("isVisible", request).await?;
+        Ok(Response::new(res))
+    }
+
+    async fn batch_is_visible(
+        &self,
+        request: Request<land::v0::BatchIsVisibleRequest>,
+    ) -> Result<Response<land::v0::BatchIsVisibleResponse>, Status> {
+        let res = self.request("batchIsVisible", request).await?;
+        Ok(Response::new(res))
+    }
+
+    async fn get_intersection_point(
+        &self,
+        request: Request<land::v0::GetIntersectionPointRequest>,
+    ) -> Result<Response<land::v0::GetIntersectionPointResponse>, Status> {
+        let res = self.request("getIntersectionPoint", request).await?;
+        Ok(Response::new(res))
+    }
+
+    async fn batch_get_intersection_point(
+        &self,
+        request: Request<land::v0::BatchGetIntersectionPointRequest>,
+    ) -> Result<Response<land::v0::BatchGetIntersectionPointResponse>, Status> {
+        let res = self.request("batchGetIntersectionPoint", request).await?;
+        Ok(Response::new(res))
+    }
+
+    async fn get_profile(
+        &self,
+        request: Request<land::v0::GetProfileRequest>,
+    ) -> Result<Response<land::v0::GetProfileResponse>, Status> {
+        let res = self.request("getProfile", request).await?;
+        Ok(Response::new(res))
+    }
+
+    async fn get_closest_point_on_roads(
+        &self,
+        request: Request<land::v0::GetClosestPointOnRoadsRequest>,
+    ) -> Result<Response<land::v0::GetClosestPointOnRoadsResponse>, Status> {
+        let res = self.request("getClosestPointOnRoads", request).await?;
+        Ok(Response::new(res))
+    }
+
+    async fn find_path_on_roads(
+        &self,
+        request: Request<land::v0::FindPathOnRoadsRequest>,
+    ) -> Result<Response<land::v0::FindPathOnRoadsResponse>, Status> {
+        let res = self.request("findPathOnRoads", request).await?;
+        Ok(Response::new(res))
+    }
+
+    async fn path_to_ground_route(
+        &self,
+        request: Request<land::v0::PathToGroundRouteRequest>,
+    ) -> Result<Response<land::v0::PathToGroundRouteResponse>, Status> {
+        let waypoints = ground_route(&request.into_inner()).map_err(Status::invalid_argument)?;
+        Ok(Response::new(land::v0::PathToGroundRouteResponse {
+            waypoints,
+        }))
+    }
+}
+
+/// Converts the path into waypoints following the roads or railroads, skipping positions closer
+/// than `min_distance` to the previous waypoint (except for the last one).
+fn ground_route(request: &land::v0::PathToGroundRouteRequest) -> Result<Vec<Point>, String> {
+    if request.path.len() < 2 {
+        return Err("path must contain at least two positions".to_string());
+    }
+    if request.speed < 0.0 || request.speed.is_nan() {
+        return Err("speed must not be negative".to_string());
+    }
+
+    let action = match request.road_type() {
+        RoadType::Unspecified => return Err("road type must be specified".to_string()),
+        RoadType::Roads => "On Road",
+        RoadType::Railroads => "On Railroads",
+    };
+    let min_distance = request.min_distance.unwrap_or(0.0);
+    let last = request.path.len() - 1;
+    let mut previous: Option<&InputPosition> = None;
+    let mut waypoints = Vec::new();
+    for (i, position) in request.path.iter().enumerate() {
+        if i != last && previous.is_some_and(|p| distance(p, position) < min_distance) {
+            continue;
+        }
+        previous = Some(position);
+        waypoints.push(Point {
+            position: Some(*position),
+            altitude_type: AltitudeType::Barometric.into(),
+            r#type: PointType::TurningPoint.into(),
+            action: action.to_string(),
+            form: action.to_string(),
+            speed: request.speed,
+            ..Default::default()
+        });
+    }
+
+    Ok(waypoints)
+}
+
+/// The great-circle distance in meters between two positions (ignoring their altitude).
+fn distance(a: &InputPosition, b: &InputPosition) -> f64 {
+    const EARTH_RADIUS: f64 = 6_371_000.0;
+    let (lat_a, lat_b) = (a.lat.to_radians(), b.lat.to_radians());
+    let d_lat = lat_b - lat_a;
+    let d_lon = (b.lon - a.lon).to_radians();
+    let h = (d_lat / 2.0).sin().powi(2) + lat_a.cos() * lat_b.cos() * (d_lon / 2.0).sin().powi(2);
+    2.0 * EARTH_RADIUS * h.sqrt().asin()
+}
+
+#[cfg(test)]
+mod tests {
+    use stubs::land::v0::PathToGroundRouteRequest;
+
+    use super::*;
+
+    /// A position `meters` north of 42°N 41°E.
+    fn position(meters: f64) -> InputPosition {
+        InputPosition {
+            lat: 42.0 + meters / 111_195.0,
+            lon: 41.0,
+            alt: 0.0,
+        }
+    }
+
+    fn request(path: Vec<InputPosition>, min_distance: Option<f64>) -> PathToGroundRouteRequest {
+        let mut request = PathToGroundRouteRequest {
+            path,
+            speed: 10.0,
+            min_distance,
+            ..Default::default()
+        };
+        request.set_road_type(RoadType::Roads);
+        request
+    }
+
+    #[test]
+    fn test_distance() {
+        let a = position(0.0);
+        assert_eq!(distance(&a, &a), 0.0);
+        // one degree of latitude
+        assert!((distance(&a, &position(111_195.0)) - 111_195.0).abs() < 1.0);
+        // one degree of longitude at the equator, in both directions
+        let west = InputPosition::default();
+        let east = InputPosition {
+            lon: 1.0,
+            ..Default::default()
+        };
+        assert!((distance(&west, &east) - 111_195.0).abs() < 1.0);
+        assert_eq!(distance(&west, &east), distance(&east, &west));
+    }
+
+    #[test]
+    fn test_min_distance() {
+        let path = vec![
+            position(0.0),
+            position(50.0),
+            position(150.0),
+            position(200.0),
+            position(210.0),
+        ];
+
+        let waypoints = ground_route(&request(path.clone(), None)).unwrap();
+        assert_eq!(waypoints.len(), 5);
+
+        // the last position is kept even though it is too close to the previous waypoint
+        let waypoints = ground_route(&request(path, Some(100.0))).unwrap();
+        let positions: Vec<_> = waypoints.iter().map(|w| w.position.unwrap()).collect();
+        assert_eq!(positions, [position(0.0), position(150.0), position(210.0)]);
+    }
+
+    #[test]
+    fn test_road_type() {
+        let path = vec![position(0.0), position(100.0)];
+
+        let waypoints = ground_route(&request(path.clone(), None)).unwrap();
+        for waypoint in &waypoints {
+            assert_eq!(waypoint.action, "On Road");
+            assert_eq!(waypoint.form, "On Road");
+            assert_eq!(waypoint.r#type(), PointType::TurningPoint);
+            assert_eq!(waypoint.speed, 10.0);
+        }
+
+        let mut railroads = request(path.clone(), None);
+        railroads.set_road_type(RoadType::Railroads);
+        let waypoints = ground_route(&railroads).unwrap();
+        for waypoint in &waypoints {
+            assert_eq!(waypoint.action, "On Railroads");
+            assert_eq!(waypoint.form, "On Railroads");
+        }
+
+        let mut unspecified = request(path, None);
+        unspecified.set_road_type(RoadType::Unspecified);
+        assert_eq!(
+            ground_route(&unspecified).unwrap_err(),
+            "road type must be specified"
+        );
+    }
+
+    #[test]
+    fn test_invalid_request() {
+        assert!(ground_route(&request(vec![position(0.0)], None)).is_err());
+
+        let mut request = request(vec![position(0.0), position(100.0)], None);
+        request.speed = -1.0;
+        assert_eq!(
+            ground_route(&request).unwrap_err(),
+            "speed must not be negative"
+        );
+    }
+}
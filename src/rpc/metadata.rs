@@ -10,8 +10,7 @@ impl MetadataService for MissionRpc {
         &self,
         _request: Request<metadata::v0::GetHealthRequest>,
     ) -> Result<Response<metadata::v0::GetHealthResponse>, Status> {
-        let alive: bool = true;
-        return Ok(Response::new(metadata::v0::GetHealthResponse { alive }));
+        return Ok(Response::new(self.health.report()));
     }
 
     async fn get_version(
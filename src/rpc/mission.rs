@@ -1,13 +1,12 @@
 use std::pin::Pin;
 
-use futures_util::{Stream, StreamExt};
+use futures_util::Stream;
 use stubs::mission::v0::mission_service_server::MissionService;
 use stubs::timer::v0::timer_service_server::TimerService;
 use stubs::trigger::v0::trigger_service_server::TriggerService;
 use stubs::*;
 use time::format_description::well_known::Rfc3339;
 use time::{Date, Duration, Month, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset};
-use tokio::sync::mpsc;
 use tokio_stream::wrappers::ReceiverStream;
 use tonic::{Request, Response, Status};
 
@@ -35,10 +34,19 @@ impl MissionService for MissionRpc {
 
     async fn stream_events(
         &self,
-        _request: Request<mission::v0::StreamEventsRequest>,
+        request: Request<mission::v0::StreamEventsRequest>,
     ) -> Result<Response<Self::StreamEventsStream>, Status> {
+        let subscriber = match request.remote_addr() {
+            Some(addr) => format!("StreamEvents subscriber {addr}"),
+            None => String::from("StreamEvents subscriber"),
+        };
+        let (subscriber, rx) = self.subscribe(subscriber);
+        // subscribe before returning, so that the subscriber doesn't miss any event emitted right
+        // after the response
         let events = self.events().await;
-        let stream = AbortableStream::new(self.shutdown_signal.signal(), events.map(Ok));
+        tokio::spawn(crate::stream::stream_events(events, subscriber));
+
+        let stream = AbortableStream::new(self.shutdown_signal.signal(), ReceiverStream::new(rx));
         Ok(Response::new(Box::pin(stream)))
     }
 
@@ -46,16 +54,16 @@ impl MissionService for MissionRpc {
         &self,
         request: Request<mission::v0::StreamUnitsRequest>,
     ) -> Result<Response<Self::StreamUnitsStream>, Status> {
-        let rpc = self.clone();
-        let (tx, rx) = mpsc::channel(128);
-        tokio::spawn(async move {
-            if let Err(crate::stream::Error::Status(err)) =
-                crate::stream::stream_units(request.into_inner(), rpc, tx.clone()).await
-            {
-                // ignore error, as we don't care at this point whether the channel is closed or not
-                let _ = tx.send(Err(err)).await;
-            }
-        });
+        let subscriber = match request.remote_addr() {
+            Some(addr) => format!("StreamUnits subscriber {addr}"),
+            None => String::from("StreamUnits subscriber"),
+        };
+        let (subscriber, rx) = self.subscribe(subscriber);
+        tokio::spawn(crate::stream::stream_units(
+            request.into_inner(),
+            self.clone(),
+            subscriber,
+        ));
 
         let stream = AbortableStream::new(self.shutdown_signal.signal(), ReceiverStream::new(rx));
         Ok(Response::new(Box::pin(stream)))
@@ -161,6 +169,71 @@ impl MissionService for MissionRpc {
         Ok(Response::new(res))
     }
 
+    async fn add_mission_other_command(
+        &self,
+        request: Request<mission::v0::AddMissionOtherCommandRequest>,
+    ) -> Result<Response<mission::v0::AddMissionOtherCommandResponse>, Status> {
+        if request.get_ref().name.is_empty() {
+            return Err(Status::invalid_argument("name must not be empty"));
+        }
+        let res = self.request("addMissionOtherCommand", request).await?;
+        Ok(Response::new(res))
+    }
+
+    async fn remove_mission_other_command(
+        &self,
+        request: Request<mission::v0::RemoveMissionOtherCommandRequest>,
+    ) -> Result<Response<mission::v0::RemoveMissionOtherCommandResponse>, Status> {
+        let res = self.request("removeMissionOtherCommand", request).await?;
+        Ok(Response::new(res))
+    }
+
+    async fn add_coalition_other_command(
+        &self,
+        request: Request<mission::v0::AddCoalitionOtherCommandRequest>,
+    ) -> Result<Response<mission::v0::AddCoalitionOtherCommandResponse>, Status> {
+        if request.get_ref().name.is_empty() {
+            return Err(Status::invalid_argument("name must not be empty"));
+        }
+        let res = self.request("addCoalitionOtherCommand", request).await?;
+        Ok(Response::new(res))
+    }
+
+    async fn remove_coalition_other_command(
+        &self,
+        request: Request<mission::v0::RemoveCoalitionOtherCommandRequest>,
+    ) -> Result<Response<mission::v0::RemoveCoalitionOtherCommandResponse>, Status> {
+        let res = self.request("removeCoalitionOtherCommand", request).await?;
+        Ok(Response::new(res))
+    }
+
+    async fn add_group_other_command(
+        &self,
+        request: Request<mission::v0::AddGroupOtherCommandRequest>,
+    ) -> Result<Response<mission::v0::AddGroupOtherCommandResponse>, Status> {
+        if request.get_ref().name.is_empty() {
+            return Err(Status::invalid_argument("name must not be empty"));
+        }
+        let res = self.request("addGroupOtherCommand", request).await?;
+        Ok(Response::new(res))
+    }
+
+    async fn remove_group_other_command(
+        &self,
+        request: Request<mission::v0::RemoveGroupOtherCommandRequest>,
+    ) -> Result<Response<mission::v0::RemoveGroupOtherCommandResponse>, Status> {
+        let res = self.request("removeGroupOtherCommand", request).await?;
+        Ok(Response::new(res))
+    }
+
+    async fn list_commands(
+        &self,
+        request: Request<mission::v0::ListCommandsRequest>,
+    ) -> Result<Response<mission::v0::ListCommandsResponse>, Status> {
+        let res = self.request("listCommands", request).await?;
+        Ok(Response::new(res))
+    }
+
     async fn get_session_id(
         &self,
         _request: Request<mission::v0::GetSessionIdRequest>,
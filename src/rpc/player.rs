@@ -0,0 +1,888 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use dcs_module_ipc::IPC;
+use futures_util::StreamExt;
+use stubs::common::v0::{Initiator, Unit, initiator};
+use stubs::mission::v0::StreamEventsResponse;
+use stubs::mission::v0::stream_events_response::Event;
+use stubs::net::v0::{GetPlayersRequest, GetPlayersResponse};
+use stubs::player;
+use stubs::player::v0::player_service_server::PlayerService;
+use stubs::player::v0::{PlayerStats, Session, Sortie};
+use tonic::{Request, Response, Status};
+
+use crate::config::PlayersConfig;
+use crate::shutdown::ShutdownHandle;
+
+/// Tracks the sessions and sorties of players from the mission events, persisted as JSON file.
+#[derive(Clone)]
+pub struct Players(Arc<Inner>);
+
+struct Inner {
+    path: PathBuf,
+    state: Mutex<State>,
+    /// Serializes writes to the file.
+    write_lock: tokio::sync::Mutex<()>,
+}
+
+#[derive(Default, serde::Serialize, serde::Deserialize)]
+struct State {
+    /// All sessions, ordered by id.
+    sessions: Vec<Session>,
+    /// All sorties, ordered by id.
+    sorties: Vec<Sortie>,
+    /// The connected players (by player id).
+    #[serde(skip)]
+    online: HashMap<u32, OnlinePlayer>,
+    /// The sorties in progress (by unit name).
+    #[serde(skip)]
+    active: HashMap<String, ActiveSortie>,
+    /// The number of sessions and sorties kept.
+    #[serde(skip)]
+    retention: Retention,
+    /// Whether there are changes not written to the file yet.
+    #[serde(skip)]
+    dirty: bool,
+}
+
+struct OnlinePlayer {
+    /// The id of the player's session.
+    session_id: u64,
+    /// The slot the player is currently in, if known.
+    slot: Option<String>,
+}
+
+struct ActiveSortie {
+    /// The id of the sortie.
+    id: u64,
+    /// The mission time of the takeoff, if the unit is currently in the air.
+    airborne_since: Option<f64>,
+}
+
+#[derive(Clone, Copy)]
+struct Retention {
+    max_sessions: usize,
+    max_sorties: usize,
+}
+
+impl Default for Retention {
+    fn default() -> Self {
+        Self {
+            max_sessions: usize::MAX,
+            max_sorties: usize::MAX,
+        }
+    }
+}
+
+impl Players {
+    /// Load the sessions and sorties from the given file (starting without any history if the
+    /// file doesn't exist yet). A file that can't be loaded doesn't prevent the server from
+    /// starting; it is moved aside and the history starts empty instead.
+    pub fn load(path: PathBuf, config: &PlayersConfig) -> Self {
+        let mut state = match read(&path) {
+            Ok(state) => state,
+            Err(err) => {
+                log::error!(
+                    "Failed to load player sessions from `{}`, starting without any history: {}",
+                    path.display(),
+                    err
+                );
+                super::move_aside(&path);
+                State::default()
+            }
+        };
+        log::info!(
+            "Loaded {} player sessions and {} sorties from `{}`",
+            state.sessions.len(),
+            state.sorties.len(),
+            path.display()
+        );
+
+        // Sessions and sorties left open were interrupted (e.g. by DCS crashing), so end them at
+        // the time the file was written the last time.
+        let last_write = std::fs::metadata(&path)
+            .and_then(|meta| meta.modified())
+            .ok()
+            .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+            .map(|time| time.as_secs())
+            .unwrap_or_else(now);
+        for session in &mut state.sessions {
+            session.disconnected_at.get_or_insert(last_write);
+        }
+        for sortie in &mut state.sorties {
+            sortie.ended_at.get_or_insert(last_write);
+        }
+
+        state.retention = Retention {
+            max_sessions: config.max_sessions.max(1),
+            max_sorties: config.max_sorties.max(1),
+        };
+        state.prune();
+
+        Self(Arc::new(Inner {
+            path,
+            state: Mutex::new(state),
+            write_lock: Default::default(),
+        }))
+    }
+
+    /// Feed all mission events into the tracker until the server shuts down. Changes are written
+    /// to the file periodically (and by [Players::stop]).
+    pub async fn run_in_background(
+        self,
+        ipc: IPC<StreamEventsResponse>,
+        shutdown_signal: ShutdownHandle,
+    ) {
+        let events = ipc.events().await;
+        let mut events = std::pin::pin!(events);
+        let mut shutdown_signal = shutdown_signal.signal();
+        let mut interval = tokio::time::interval(Duration::from_secs(30));
+
+        loop {
+            let event = tokio::select! {
+                _ = &mut shutdown_signal => break,
+                _ = interval.tick() => {
+                    self.save().await;
+                    continue;
+                }
+                event = events.next() => event,
+            };
+            let Some(event) = event else {
+                break;
+            };
+            let Some(kind) = event.event else {
+                continue;
+            };
+
+            // Players that connected before the server started (e.g. before a mission restart)
+            // are unknown until looked up.
+            if let Event::Birth(birth) = &kind {
+                let player_name = unit(&birth.initiator).and_then(|unit| unit.player_name.clone());
+                if player_name.is_some_and(|name| !self.is_online(&name)) {
+                    self.sync_online(&ipc).await;
+                }
+            }
+
+            self.0.state.lock().unwrap().handle(event.time, kind);
+        }
+    }
+
+    /// End all sessions and sorties and write them to the file. Connected players are only known
+    /// again after a restart once they spawn, so their sessions are ended here.
+    pub async fn stop(&self) {
+        {
+            let mut state = self.0.state.lock().unwrap();
+            state.end_all_sorties(None);
+            let now = now();
+            for player in std::mem::take(&mut state.online).into_values() {
+                if let Some(session) = state.session(player.session_id) {
+                    session.disconnected_at = Some(now);
+                }
+            }
+            state.dirty = true;
+        }
+        self.save().await;
+    }
+
+    fn is_online(&self, player_name: &str) -> bool {
+        let state = self.0.state.lock().unwrap();
+        state.online.values().any(|player| {
+            state
+                .find_session(player.session_id)
+                .is_some_and(|session| session.player_name == player_name)
+        })
+    }
+
+    /// Start sessions for all connected players that are not known yet.
+    async fn sync_online(&self, ipc: &IPC<StreamEventsResponse>) {
+        let players = match ipc
+            .request::<_, GetPlayersResponse>("getPlayers", Some(GetPlayersRequest {}))
+            .await
+        {
+            Ok(res) => res.players,
+            Err(err) => {
+                log::warn!("Failed to retrieve connected players: {}", err);
+                return;
+            }
+        };
+
+        let mut state = self.0.state.lock().unwrap();
+        for player in players {
+            if player.ucid.is_empty() {
+                continue;
+            }
+            if !state.online.contains_key(&player.id) {
+                state.connect(player.id, player.ucid, player.name);
+            }
+            if let Some(online) = state.online.get_mut(&player.id) {
+                online.slot = Some(player.slot).filter(|slot| !slot.is_empty());
+            }
+        }
+    }
+
+    /// Write all sessions and sorties to the file, if anything changed. The file is replaced
+    /// atomically, so that a crash while writing doesn't corrupt it.
+    async fn save(&self) {
+        let _lock = self.0.write_lock.lock().await;
+        let json = {
+            let mut state = self.0.state.lock().unwrap();
+            if !state.dirty {
+                return;
+            }
+            state.dirty = false;
+            serde_json::to_vec(&*state)
+        };
+        let json = match json {
+            Ok(json) => json,
+            Err(err) => {
+                log::error!("Failed to serialize player sessions: {}", err);
+                return;
+            }
+        };
+
+        let tmp = self.0.path.with_extension("json.tmp");
+        let write = async {
+            if let Some(dir) = self.0.path.parent() {
+                tokio::fs::create_dir_all(dir).await?;
+            }
+            tokio::fs::write(&tmp, json).await?;
+            tokio::fs::rename(&tmp, &self.0.path).await
+        };
+        if let Err(err) = write.await {
+            log::error!(
+                "Failed to write player sessions to `{}`: {}",
+                self.0.path.display(),
+                err
+            );
+            self.0.state.lock().unwrap().dirty = true;
+        }
+    }
+}
+
+impl State {
+    fn handle(&mut self, time: f64, event: Event) {
+        match event {
+            Event::Connect(event) => self.connect(event.id, event.ucid, event.name),
+            Event::Disconnect(event) => {
+                if let Some(player) = self.online.remove(&event.id) {
+                    if let Some(ucid) = self.find_session(player.session_id).map(|s| s.ucid.clone())
+                    {
+                        self.end_all_sorties(Some((ucid.as_str(), time)));
+                    }
+                    if let Some(session) = self.session(player.session_id) {
+                        session.disconnected_at = Some(now());
+                    }
+                    self.dirty = true;
+                }
+            }
+            Event::PlayerChangeSlot(event) => {
+                let Some(player) = self.online.get_mut(&event.player_id) else {
+                    return;
+                };
+                player.slot = Some(event.slot_id).filter(|slot| !slot.is_empty());
+                let session_id = player.session_id;
+                if let Some(ucid) = self.find_session(session_id).map(|s| s.ucid.clone()) {
+                    self.end_all_sorties(Some((ucid.as_str(), time)));
+                }
+            }
+            Event::Birth(event) => {
+                if let Some(unit) = unit(&event.initiator) {
+                    self.start_sortie(unit, time);
+                }
+            }
+            Event::Takeoff(event) => self.takeoff(&event.initiator, time),
+            Event::RunwayTakeoff(event) => self.takeoff(&event.initiator, time),
+            Event::Land(event) => self.land(&event.initiator, time),
+            Event::RunwayTouch(event) => self.land(&event.initiator, time),
+            Event::LandingQualityMark(event) => {
+                if let Some(sortie) = self.active_sortie(&event.initiator) {
+                    sortie.landing_quality_marks.push(event.comment);
+                }
+            }
+            Event::Kill(event) => {
+                if let Some(sortie) = self.active_sortie(&event.initiator) {
+                    sortie.kills += 1;
+                }
+            }
+            Event::Ejection(event) => {
+                if let Some(sortie) = self.active_sortie(&event.initiator) {
+                    sortie.ejections = 1;
+                }
+                self.end_sortie(&event.initiator, time);
+            }
+            Event::PilotDead(event) => self.died(&event.initiator, time),
+            Event::Crash(event) => self.died(&event.initiator, time),
+            Event::Dead(event) => self.died(&event.initiator, time),
+            Event::UnitLost(event) => self.died(&event.initiator, time),
+            Event::PlayerLeaveUnit(event) => self.end_sortie(&event.initiator, time),
+            Event::MissionEnd(_) => self.end_all_sorties(None),
+            _ => {}
+        }
+    }
+
+    fn connect(&mut self, id: u32, ucid: String, player_name: String) {
+        let session_id = self.sessions.last().map_or(1, |s| s.id + 1);
+        self.sessions.push(Session {
+            id: session_id,
+            ucid,
+            player_name,
+            connected_at: now(),
+            disconnected_at: None,
+        });
+        self.online.insert(
+            id,
+            OnlinePlayer {
+                session_id,
+                slot: None,
+            },
+        );
+        self.prune();
+        self.dirty = true;
+    }
+
+    /// Find the session of the player in the given unit. The player is identified by the slot
+    /// they changed into, as only the player name is known for a unit, which isn't unique. The
+    /// name is only used as fallback if it is unique among the connected players.
+    fn player_session(&self, unit: &Unit) -> Option<&Session> {
+        let player_name = unit.player_name.as_deref()?;
+        let unit_id = unit.id.to_string();
+        let in_slot = |slot: &str| {
+            // multi-crew slots are suffixed with the seat, e.g. `12_2`
+            slot == unit_id || slot.split_once('_').is_some_and(|(id, _)| id == unit_id)
+        };
+        let candidates = self
+            .online
+            .values()
+            .filter_map(|player| Some((player, self.find_session(player.session_id)?)))
+            .filter(|(_, session)| session.player_name == player_name)
+            .collect::<Vec<_>>();
+
+        if let Some((_, session)) = candidates
+            .iter()
+            .find(|(player, _)| player.slot.as_deref().is_some_and(in_slot))
+        {
+            return Some(session);
+        }
+        match candidates.as_slice() {
+            [(_, session)] => Some(session),
+            _ => None,
+        }
+    }
+
+    fn start_sortie(&mut self, unit: &Unit, time: f64) {
+        let Some(player_name) = &unit.player_name else {
+            return;
+        };
+        let Some(session) = self.player_session(unit) else {
+            log::debug!(
+                "Not tracking sortie of unknown or ambiguous player `{}`",
+                player_name
+            );
+            return;
+        };
+
+        let session_id = session.id;
+        let ucid = session.ucid.clone();
+        // a player can only be in one unit at a time
+        self.end_all_sorties(Some((ucid.as_str(), time)));
+
+        let id = self.sorties.last().map_or(1, |s| s.id + 1);
+        self.sorties.push(Sortie {
+            id,
+            session_id,
+            ucid,
+            player_name: player_name.clone(),
+            unit_name: unit.name.clone(),
+            unit_type: unit.r#type.clone(),
+            coalition: unit.coalition,
+            started_at: now(),
+            ..Default::default()
+        });
+        self.active.insert(
+            unit.name.clone(),
+            ActiveSortie {
+                id,
+                airborne_since: None,
+            },
+        );
+        self.prune();
+        self.dirty = true;
+    }
+
+    fn find_session(&self, id: u64) -> Option<&Session> {
+        let index = self.sessions.binary_search_by_key(&id, |s| s.id).ok()?;
+        Some(&self.sessions[index])
+    }
+
+    fn session(&mut self, id: u64) -> Option<&mut Session> {
+        let index = self.sessions.binary_search_by_key(&id, |s| s.id).ok()?;
+        Some(&mut self.sessions[index])
+    }
+
+    fn sortie(&mut self, id: u64) -> Option<&mut Sortie> {
+        let index = self.sorties.binary_search_by_key(&id, |s| s.id).ok()?;
+        Some(&mut self.sorties[index])
+    }
+
+    fn active_sortie(&mut self, initiator: &Option<Initiator>) -> Option<&mut Sortie> {
+        let id = self.active.get(&unit(initiator)?.name)?.id;
+        self.dirty = true;
+        self.sortie(id)
+    }
+
+    fn takeoff(&mut self, initiator: &Option<Initiator>, time: f64) {
+        if let Some(active) = unit(initiator).and_then(|unit| self.active.get_mut(&unit.name)) {
+            // takeoff and runway takeoff events are both fired for the same takeoff
+            active.airborne_since.get_or_insert(time);
+        }
+    }
+
+    fn land(&mut self, initiator: &Option<Initiator>, time: f64) {
+        let Some(active) = unit(initiator).and_then(|unit| self.active.get_mut(&unit.name)) else {
+            return;
+        };
+        // land and runway touch events are both fired for the same landing
+        if let Some(since) = active.airborne_since.take() {
+            let id = active.id;
+            if let Some(sortie) = self.sortie(id) {
+                sortie.flight_time += (time - since).max(0.0);
+                sortie.landings += 1;
+            }
+            self.dirty = true;
+        }
+    }
+
+    fn died(&mut self, initiator: &Option<Initiator>, time: f64) {
+        if let Some(sortie) = self.active_sortie(initiator) {
+            sortie.deaths = 1;
+        }
+        self.end_sortie(initiator, time);
+    }
+
+    fn end_sortie(&mut self, initiator: &Option<Initiator>, time: f64) {
+        let Some(active) = unit(initiator).and_then(|unit| self.active.remove(&unit.name)) else {
+            return;
+        };
+        self.finish(active, Some(time));
+    }
+
+    /// End all sorties (of the given player only, if set).
+    fn end_all_sorties(&mut self, of: Option<(&str, f64)>) {
+        let unit_names = self
+            .active
+            .iter()
+            .filter(|(_, active)| {
+                of.is_none_or(|(ucid, _)| {
+                    self.sorties
+                        .binary_search_by_key(&active.id, |s| s.id)
+                        .is_ok_and(|index| self.sorties[index].ucid == ucid)
+                })
+            })
+            .map(|(unit_name, _)| unit_name.clone())
+            .collect::<Vec<_>>();
+        for unit_name in unit_names {
+            if let Some(active) = self.active.remove(&unit_name) {
+                self.finish(active, of.map(|(_, time)| time));
+            }
+        }
+    }
+
+    /// Finish the sortie. The flight time of sorties ending in the air is only counted if the
+    /// mission time of the end is known.
+    fn finish(&mut self, active: ActiveSortie, time: Option<f64>) {
+        if let Some(sortie) = self.sortie(active.id) {
+            if let (Some(since), Some(time)) = (active.airborne_since, time) {
+                sortie.flight_time += (time - since).max(0.0);
+            }
+            sortie.ended_at = Some(now());
+        }
+        self.dirty = true;
+    }
+
+    /// Remove the oldest ended sessions and sorties beyond the retention limits. Sessions and
+    /// sorties still in progress are always kept.
+    fn prune(&mut self) {
+        let removed = prune(&mut self.sessions, self.retention.max_sessions, |s| {
+            s.disconnected_at.is_none()
+        }) + prune(&mut self.sorties, self.retention.max_sorties, |s| {
+            s.ended_at.is_none()
+        });
+        if removed > 0 {
+            self.dirty = true;
+        }
+    }
+
+    fn stats(&self, ucid: &str) -> Option<PlayerStats> {
+        let now = now();
+        let mut stats = PlayerStats {
+            ucid: ucid.to_string(),
+            ..Default::default()
+        };
+        for session in self.sessions.iter().filter(|s| s.ucid == ucid) {
+            stats.player_name.clone_from(&session.player_name);
+            stats.sessions += 1;
+            stats.online_time += session
+                .disconnected_at
+                .unwrap_or(now)
+                .saturating_sub(session.connected_at) as f64;
+        }
+        if stats.sessions == 0 {
+            return None;
+        }
+        for sortie in self.sorties.iter().filter(|s| s.ucid == ucid) {
+            stats.sorties += 1;
+            stats.flight_time += sortie.flight_time;
+            stats.kills += sortie.kills;
+            stats.deaths += sortie.deaths;
+            stats.ejections += sortie.ejections;
+            stats.landings += sortie.landings;
+        }
+        Some(stats)
+    }
+}
+
+/// Remove the oldest entries beyond `max` that are not `in_progress`. Returns the number of
+/// removed entries.
+fn prune<T>(entries: &mut Vec<T>, max: usize, in_progress: impl Fn(&T) -> bool) -> usize {
+    let excess = entries.len().saturating_sub(max);
+    let mut removed = 0;
+    entries.retain(|entry| {
+        if removed < excess && !in_progress(entry) {
+            removed += 1;
+            false
+        } else {
+            true
+        }
+    });
+    removed
+}
+
+fn read(path: &Path) -> Result<State, std::io::Error> {
+    match std::fs::read(path) {
+        Ok(json) => serde_json::from_slice::<State>(&json)
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err)),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(State::default()),
+        Err(err) => Err(err),
+    }
+}
+
+#[tonic::async_trait]
+impl PlayerService for Players {
+    async fn list_sessions(
+        &self,
+        request: Request<player::v0::ListSessionsRequest>,
+    ) -> Result<Response<player::v0::ListSessionsResponse>, Status> {
+        let request = request.into_inner();
+        let sessions = self
+            .0
+            .state
+            .lock()
+            .unwrap()
+            .sessions
+            .iter()
+            .filter(|s| request.ucid.as_ref().is_none_or(|ucid| &s.ucid == ucid))
+            .filter(|s| !request.active_only || s.disconnected_at.is_none())
+            .cloned()
+            .collect();
+        Ok(Response::new(player::v0::ListSessionsResponse { sessions }))
+    }
+
+    async fn list_sorties(
+        &self,
+        request: Request<player::v0::ListSortiesRequest>,
+    ) -> Result<Response<player::v0::ListSortiesResponse>, Status> {
+        let request = request.into_inner();
+        let sorties = self
+            .0
+            .state
+            .lock()
+            .unwrap()
+            .sorties
+            .iter()
+            .filter(|s| request.ucid.as_ref().is_none_or(|ucid| &s.ucid == ucid))
+            .filter(|s| !request.active_only || s.ended_at.is_none())
+            .cloned()
+            .collect();
+        Ok(Response::new(player::v0::ListSortiesResponse { sorties }))
+    }
+
+    async fn get_player_stats(
+        &self,
+        request: Request<player::v0::GetPlayerStatsRequest>,
+    ) -> Result<Response<player::v0::GetPlayerStatsResponse>, Status> {
+        let ucid = request.into_inner().ucid;
+        let stats = self
+            .0
+            .state
+            .lock()
+            .unwrap()
+            .stats(&ucid)
+            .ok_or_else(|| Status::not_found(format!("player `{ucid}` not found")))?;
+        Ok(Response::new(player::v0::GetPlayerStatsResponse {
+            stats: Some(stats),
+        }))
+    }
+}
+
+fn unit(initiator: &Option<Initiator>) -> Option<&Unit> {
+    match initiator.as_ref()?.initiator.as_ref()? {
+        initiator::Initiator::Unit(unit) => Some(unit),
+        _ => None,
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use stubs::mission::v0::stream_events_response::{
+        BirthEvent, ConnectEvent, DisconnectEvent, EjectionEvent, KillEvent, LandEvent,
+        PlayerChangeSlotEvent, RunwayTakeoffEvent, RunwayTouchEvent, TakeoffEvent,
+    };
+
+    use super::*;
+
+    fn initiator(unit_id: u32, unit_name: &str, player_name: &str) -> Option<Initiator> {
+        Some(Initiator {
+            initiator: Some(initiator::Initiator::Unit(Unit {
+                id: unit_id,
+                name: unit_name.to_string(),
+                r#type: "F-16C_50".to_string(),
+                player_name: Some(player_name.to_string()),
+                ..Default::default()
+            })),
+        })
+    }
+
+    fn connect(state: &mut State, id: u32, ucid: &str, name: &str) {
+        state.handle(
+            0.0,
+            Event::Connect(ConnectEvent {
+                id,
+                ucid: ucid.to_string(),
+                name: name.to_string(),
+                ..Default::default()
+            }),
+        );
+    }
+
+    fn change_slot(state: &mut State, player_id: u32, slot_id: &str) {
+        state.handle(
+            0.0,
+            Event::PlayerChangeSlot(PlayerChangeSlotEvent {
+                player_id,
+                slot_id: slot_id.to_string(),
+                ..Default::default()
+            }),
+        );
+    }
+
+    fn birth(state: &mut State, time: f64, initiator: &Option<Initiator>) {
+        state.handle(
+            time,
+            Event::Birth(BirthEvent {
+                initiator: initiator.clone(),
+                ..Default::default()
+            }),
+        );
+    }
+
+    fn takeoff(state: &mut State, time: f64, initiator: &Option<Initiator>) {
+        state.handle(
+            time,
+            Event::Takeoff(TakeoffEvent {
+                initiator: initiator.clone(),
+                ..Default::default()
+            }),
+        );
+        state.handle(
+            time,
+            Event::RunwayTakeoff(RunwayTakeoffEvent {
+                initiator: initiator.clone(),
+                ..Default::default()
+            }),
+        );
+    }
+
+    fn land(state: &mut State, time: f64, initiator: &Option<Initiator>) {
+        state.handle(
+            time,
+            Event::RunwayTouch(RunwayTouchEvent {
+                initiator: initiator.clone(),
+                ..Default::default()
+            }),
+        );
+        state.handle(
+            time,
+            Event::Land(LandEvent {
+                initiator: initiator.clone(),
+                ..Default::default()
+            }),
+        );
+    }
+
+    #[test]
+    fn test_stats_aggregate_sorties() {
+        let mut state = State::default();
+        connect(&mut state, 2, "abc", "Hawk");
+        let viper = initiator(101, "Viper-1-1", "Hawk");
+
+        // first sortie: two flights, a kill and an ejection
+        birth(&mut state, 10.0, &viper);
+        takeoff(&mut state, 20.0, &viper);
+        land(&mut state, 80.0, &viper);
+        takeoff(&mut state, 100.0, &viper);
+        state.handle(
+            110.0,
+            Event::Kill(KillEvent {
+                initiator: viper.clone(),
+                ..Default::default()
+            }),
+        );
+        state.handle(
+            130.0,
+            Event::Ejection(EjectionEvent {
+                initiator: viper.clone(),
+                ..Default::default()
+            }),
+        );
+
+        // second sortie: ended by disconnecting mid-air
+        birth(&mut state, 200.0, &viper);
+        takeoff(&mut state, 210.0, &viper);
+        state.handle(
+            250.0,
+            Event::Disconnect(DisconnectEvent {
+                id: 2,
+                ..Default::default()
+            }),
+        );
+
+        assert_eq!(state.sorties.len(), 2);
+        assert!(state.sorties.iter().all(|s| s.ended_at.is_some()));
+        assert!(state.active.is_empty());
+        assert!(state.online.is_empty());
+
+        let stats = state.stats("abc").unwrap();
+        assert_eq!(stats.player_name, "Hawk");
+        assert_eq!(stats.sessions, 1);
+        assert_eq!(stats.sorties, 2);
+        assert_eq!(stats.flight_time, 60.0 + 30.0 + 40.0);
+        assert_eq!(stats.landings, 1);
+        assert_eq!(stats.kills, 1);
+        assert_eq!(stats.ejections, 1);
+        assert_eq!(stats.deaths, 0);
+
+        assert!(state.stats("unknown").is_none());
+    }
+
+    #[test]
+    fn test_sorties_are_attributed_by_slot() {
+        let mut state = State::default();
+        // two different players using the same name
+        connect(&mut state, 2, "abc", "Hawk");
+        connect(&mut state, 3, "def", "Hawk");
+        change_slot(&mut state, 2, "101");
+        change_slot(&mut state, 3, "102_2");
+
+        birth(&mut state, 10.0, &initiator(102, "Viper-1-2", "Hawk"));
+        birth(&mut state, 10.0, &initiator(101, "Viper-1-1", "Hawk"));
+
+        let ucid = |unit_name: &str| {
+            state
+                .sorties
+                .iter()
+                .find(|s| s.unit_name == unit_name)
+                .map(|s| s.ucid.as_str())
+        };
+        assert_eq!(ucid("Viper-1-1"), Some("abc"));
+        assert_eq!(ucid("Viper-1-2"), Some("def"));
+    }
+
+    #[test]
+    fn test_ambiguous_player_name_is_not_tracked() {
+        let mut state = State::default();
+        connect(&mut state, 2, "abc", "Hawk");
+        connect(&mut state, 3, "def", "Hawk");
+
+        // no slot known for either player
+        birth(&mut state, 10.0, &initiator(101, "Viper-1-1", "Hawk"));
+        assert!(state.sorties.is_empty());
+
+        // the name is enough once it is unique
+        connect(&mut state, 4, "ghi", "Eagle");
+        birth(&mut state, 10.0, &initiator(103, "Eagle-1-1", "Eagle"));
+        assert_eq!(state.sorties.len(), 1);
+        assert_eq!(state.sorties[0].ucid, "ghi");
+    }
+
+    #[test]
+    fn test_retention_keeps_entries_in_progress() {
+        let mut state = State {
+            retention: Retention {
+                max_sessions: 2,
+                max_sorties: 1,
+            },
+            ..Default::default()
+        };
+
+        connect(&mut state, 1, "abc", "Hawk");
+        for id in 2..=4 {
+            connect(
+                &mut state,
+                id,
+                &format!("ucid-{id}"),
+                &format!("Player {id}"),
+            );
+            state.handle(
+                0.0,
+                Event::Disconnect(DisconnectEvent {
+                    id,
+                    ..Default::default()
+                }),
+            );
+        }
+
+        // the oldest session is still connected, so only ended sessions got removed
+        let ids = state.sessions.iter().map(|s| s.id).collect::<Vec<_>>();
+        assert_eq!(ids, vec![1, 4]);
+
+        // ids are not reused after pruning
+        connect(&mut state, 5, "jkl", "Eagle");
+        assert_eq!(state.sessions.last().unwrap().id, 5);
+
+        let viper = initiator(101, "Viper-1-1", "Hawk");
+        birth(&mut state, 10.0, &viper);
+        birth(&mut state, 20.0, &viper);
+        takeoff(&mut state, 30.0, &viper);
+        land(&mut state, 40.0, &viper);
+        assert_eq!(state.sorties.len(), 1);
+        assert_eq!(state.sorties[0].id, 2);
+        assert_eq!(state.sorties[0].landings, 1);
+    }
+
+    #[test]
+    fn test_load_invalid_file() {
+        let dir =
+            std::env::temp_dir().join(format!("dcs-grpc-players-{}-invalid", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("players.json");
+        std::fs::write(&path, "{ not json").unwrap();
+
+        let players = Players::load(path.clone(), &PlayersConfig::default());
+        assert!(players.0.state.lock().unwrap().sessions.is_empty());
+        assert!(!path.exists());
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 1);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
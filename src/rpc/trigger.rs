@@ -1,4 +1,6 @@
+use stubs::common::v0::InputPosition;
 use stubs::trigger;
+use stubs::trigger::v0::Color;
 use stubs::trigger::v0::trigger_service_server::TriggerService;
 use tonic::{Request, Response, Status};
 
@@ -38,6 +40,70 @@ impl TriggerService for MissionRpc {
         Ok(Response::new(res))
     }
 
+    async fn out_text_for_country(
+        &self,
+        request: Request<trigger::v0::OutTextForCountryRequest>,
+    ) -> Result<Response<trigger::v0::OutTextForCountryResponse>, Status> {
+        let res = self.request("outTextForCountry", request).await?;
+        Ok(Response::new(res))
+    }
+
+    async fn out_sound(
+        &self,
+        request: Request<trigger::v0::OutSoundRequest>,
+    ) -> Result<Response<trigger::v0::OutSoundResponse>, Status> {
+        validate_sound(&request.get_ref().file).map_err(Status::invalid_argument)?;
+        let res = self.request("outSound", request).await?;
+        Ok(Response::new(res))
+    }
+
+    async fn out_sound_for_coalition(
+        &self,
+        request: Request<trigger::v0::OutSoundForCoalitionRequest>,
+    ) -> Result<Response<trigger::v0::OutSoundForCoalitionResponse>, Status> {
+        validate_sound(&request.get_ref().file).map_err(Status::invalid_argument)?;
+        let res = self.request("outSoundForCoalition", request).await?;
+        Ok(Response::new(res))
+    }
+
+    async fn out_sound_for_country(
+        &self,
+        request: Request<trigger::v0::OutSoundForCountryRequest>,
+    ) -> Result<Response<trigger::v0::OutSoundForCountryResponse>, Status> {
+        validate_sound(&request.get_ref().file).map_err(Status::invalid_argument)?;
+        let res = self.request("outSoundForCountry", request).await?;
+        Ok(Response::new(res))
+    }
+
+    async fn out_sound_for_group(
+        &self,
+        request: Request<trigger::v0::OutSoundForGroupRequest>,
+    ) -> Result<Response<trigger::v0::OutSoundForGroupResponse>, Status> {
+        validate_sound(&request.get_ref().file).map_err(Status::invalid_argument)?;
+        let res = self.request("outSoundForGroup", request).await?;
+        Ok(Response::new(res))
+    }
+
+    async fn radio_transmission(
+        &self,
+        request: Request<trigger::v0::RadioTransmissionRequest>,
+    ) -> Result<Response<trigger::v0::RadioTransmissionResponse>, Status> {
+        validate_transmission(request.get_ref()).map_err(Status::invalid_argument)?;
+        let res = self.request("radioTransmission", request).await?;
+        Ok(Response::new(res))
+    }
+
+    async fn stop_radio_transmission(
+        &self,
+        request: Request<trigger::v0::StopRadioTransmissionRequest>,
+    ) -> Result<Response<trigger::v0::StopRadioTransmissionResponse>, Status> {
+        if request.get_ref().name.is_empty() {
+            return Err(Status::invalid_argument("name must not be empty"));
+        }
+        let res = self.request("stopRadioTransmission", request).await?;
+        Ok(Response::new(res))
+    }
+
     async fn get_user_flag(
         &self,
         request: Request<trigger::v0::GetUserFlagRequest>,
@@ -133,4 +199,421 @@ impl TriggerService for MissionRpc {
         let res = self.request("signalFlare", request).await?;
         Ok(Response::new(res))
     }
+
+    async fn line_to_all(
+        &self,
+        request: Request<trigger::v0::LineToAllRequest>,
+    ) -> Result<Response<trigger::v0::LineToAllResponse>, Status> {
+        let req = request.get_ref();
+        validate_drawing(
+            &[("from", req.from.as_ref()), ("to", req.to.as_ref())],
+            req.color.as_ref(),
+            None,
+        )
+        .map_err(Status::invalid_argument)?;
+        let res = self.request("lineToAll", request).await?;
+        Ok(Response::new(res))
+    }
+
+    async fn circle_to_all(
+        &self,
+        request: Request<trigger::v0::CircleToAllRequest>,
+    ) -> Result<Response<trigger::v0::CircleToAllResponse>, Status> {
+        let req = request.get_ref();
+        validate_drawing(
+            &[("center", req.center.as_ref())],
+            req.color.as_ref(),
+            req.fill_color.as_ref(),
+        )
+        .and_then(|_| validate_radius(req.radius))
+        .map_err(Status::invalid_argument)?;
+        let res = self.request("circleToAll", request).await?;
+        Ok(Response::new(res))
+    }
+
+    async fn rect_to_all(
+        &self,
+        request: Request<trigger::v0::RectToAllRequest>,
+    ) -> Result<Response<trigger::v0::RectToAllResponse>, Status> {
+        let req = request.get_ref();
+        validate_drawing(
+            &[("from", req.from.as_ref()), ("to", req.to.as_ref())],
+            req.color.as_ref(),
+            req.fill_color.as_ref(),
+        )
+        .map_err(Status::invalid_argument)?;
+        let res = self.request("rectToAll", request).await?;
+        Ok(Response::new(res))
+    }
+
+    async fn quad_to_all(
+        &self,
+        request: Request<trigger::v0::QuadToAllRequest>,
+    ) -> Result<Response<trigger::v0::QuadToAllResponse>, Status> {
+        let req = request.get_ref();
+        if req.points.len() != 4 {
+            return Err(Status::invalid_argument(format!(
+                "a quad requires exactly four points, got {}",
+                req.points.len()
+            )));
+        }
+        validate_drawing(&[], req.color.as_ref(), req.fill_color.as_ref())
+            .map_err(Status::invalid_argument)?;
+        let res = self.request("quadToAll", request).await?;
+        Ok(Response::new(res))
+    }
+
+    async fn text_to_all(
+        &self,
+        request: Request<trigger::v0::TextToAllRequest>,
+    ) -> Result<Response<trigger::v0::TextToAllResponse>, Status> {
+        let req = request.get_ref();
+        validate_drawing(
+            &[("position", req.position.as_ref())],
+            req.color.as_ref(),
+            req.fill_color.as_ref(),
+        )
+        .and_then(|_| validate_text(&req.text, req.font_size))
+        .map_err(Status::invalid_argument)?;
+        let res = self.request("textToAll", request).await?;
+        Ok(Response::new(res))
+    }
+
+    async fn arrow_to_all(
+        &self,
+        request: Request<trigger::v0::ArrowToAllRequest>,
+    ) -> Result<Response<trigger::v0::ArrowToAllResponse>, Status> {
+        let req = request.get_ref();
+        validate_drawing(
+            &[("from", req.from.as_ref()), ("to", req.to.as_ref())],
+            req.color.as_ref(),
+            req.fill_color.as_ref(),
+        )
+        .map_err(Status::invalid_argument)?;
+        let res = self.request("arrowToAll", request).await?;
+        Ok(Response::new(res))
+    }
+
+    async fn update_drawing(
+        &self,
+        request: Request<trigger::v0::UpdateDrawingRequest>,
+    ) -> Result<Response<trigger::v0::UpdateDrawingResponse>, Status> {
+        validate_update(request.get_ref()).map_err(Status::invalid_argument)?;
+        let res = self.request("updateDrawing", request).await?;
+        Ok(Response::new(res))
+    }
+
+    async fn list_drawings(
+        &self,
+        request: Request<trigger::v0::ListDrawingsRequest>,
+    ) -> Result<Response<trigger::v0::ListDrawingsResponse>, Status> {
+        let res = self.request("listDrawings", request).await?;
+        Ok(Response::new(res))
+    }
+
+    async fn clear_drawings(
+        &self,
+        request: Request<trigger::v0::ClearDrawingsRequest>,
+    ) -> Result<Response<trigger::v0::ClearDrawingsResponse>, Status> {
+        let res = self.request("clearDrawings", request).await?;
+        Ok(Response::new(res))
+    }
+}
+
+fn validate_sound(file: &str) -> Result<(), String> {
+    if file.trim().is_empty() {
+        return Err("file must not be empty".to_string());
+    }
+
+    let extension = file
+        .rsplit_once('.')
+        .map(|(_, ext)| ext.to_ascii_lowercase());
+    if !matches!(extension.as_deref(), Some("ogg" | "wav")) {
+        return Err(format!("file `{file}` must be an .ogg or .wav file"));
+    }
+
+    Ok(())
+}
+
+fn validate_transmission(request: &trigger::v0::RadioTransmissionRequest) -> Result<(), String> {
+    validate_sound(&request.file)?;
+    if request.position.is_none() {
+        return Err("position must be specified".to_string());
+    }
+    if request.frequency == 0 {
+        return Err("frequency must be greater than 0".to_string());
+    }
+    if request
+        .power
+        .is_some_and(|power| power <= 0.0 || power.is_nan())
+    {
+        return Err("power must be greater than 0".to_string());
+    }
+    if request.name.as_ref().is_some_and(|name| name.is_empty()) {
+        return Err("name must not be empty".to_string());
+    }
+    if request.r#loop && request.name.is_none() {
+        return Err("looping transmissions require a name to stop them".to_string());
+    }
+
+    Ok(())
+}
+
+fn validate_drawing(
+    positions: &[(&str, Option<&InputPosition>)],
+    color: Option<&Color>,
+    fill_color: Option<&Color>,
+) -> Result<(), String> {
+    for (name, position) in positions {
+        if position.is_none() {
+            return Err(format!("{name} must be specified"));
+        }
+    }
+
+    validate_color(
+        "color",
+        color.ok_or_else(|| "color must be specified".to_string())?,
+    )?;
+    fill_color.map_or(Ok(()), |color| validate_color("fill_color", color))
+}
+
+fn validate_update(request: &trigger::v0::UpdateDrawingRequest) -> Result<(), String> {
+    if let Some(color) = &request.color {
+        validate_color("color", color)?;
+    }
+    if let Some(fill_color) = &request.fill_color {
+        validate_color("fill_color", fill_color)?;
+    }
+    if let Some(radius) = request.radius {
+        validate_radius(radius)?;
+    }
+    if request.text.as_ref().is_some_and(|text| text.is_empty()) {
+        return Err("text must not be empty".to_string());
+    }
+    if request.font_size == Some(0) {
+        return Err("font_size must be greater than 0".to_string());
+    }
+
+    Ok(())
+}
+
+fn validate_color(name: &str, color: &Color) -> Result<(), String> {
+    let components = [color.red, color.green, color.blue, color.alpha];
+    if components.iter().any(|c| !(0.0..=1.0).contains(c)) {
+        return Err(format!("{name} components must be between 0 and 1"));
+    }
+
+    Ok(())
+}
+
+fn validate_radius(radius: f64) -> Result<(), String> {
+    if radius <= 0.0 || radius.is_nan() {
+        return Err("radius must be greater than 0".to_string());
+    }
+
+    Ok(())
+}
+
+fn validate_text(text: &str, font_size: u32) -> Result<(), String> {
+    if text.is_empty() {
+        return Err("text must not be empty".to_string());
+    }
+    if font_size == 0 {
+        return Err("font_size must be greater than 0".to_string());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use stubs::trigger::v0::{RadioTransmissionRequest, UpdateDrawingRequest};
+
+    use super::*;
+
+    fn color(alpha: f64) -> Color {
+        Color {
+            red: 1.0,
+            green: 0.5,
+            blue: 0.0,
+            alpha,
+        }
+    }
+
+    fn transmission() -> RadioTransmissionRequest {
+        RadioTransmissionRequest {
+            file: "l10n/DEFAULT/beacon.ogg".to_string(),
+            position: Some(InputPosition::default()),
+            frequency: 251_000_000,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_validate_sound() {
+        validate_sound("l10n/DEFAULT/alarm.ogg").unwrap();
+        validate_sound("alarm.WAV").unwrap();
+        assert_eq!(validate_sound(" ").unwrap_err(), "file must not be empty");
+        assert!(validate_sound("alarm.mp3").is_err());
+        assert!(validate_sound("alarm").is_err());
+    }
+
+    #[test]
+    fn test_validate_transmission() {
+        validate_transmission(&transmission()).unwrap();
+        validate_transmission(&RadioTransmissionRequest {
+            r#loop: true,
+            name: Some("beacon".to_string()),
+            power: Some(10.0),
+            ..transmission()
+        })
+        .unwrap();
+
+        let invalid = [
+            RadioTransmissionRequest {
+                file: "beacon.mp3".to_string(),
+                ..transmission()
+            },
+            RadioTransmissionRequest {
+                position: None,
+                ..transmission()
+            },
+            RadioTransmissionRequest {
+                frequency: 0,
+                ..transmission()
+            },
+            RadioTransmissionRequest {
+                power: Some(0.0),
+                ..transmission()
+            },
+            RadioTransmissionRequest {
+                name: Some(String::new()),
+                ..transmission()
+            },
+        ];
+        for request in invalid {
+            assert!(validate_transmission(&request).is_err(), "{request:?}");
+        }
+    }
+
+    #[test]
+    fn test_validate_looping_transmission_without_name() {
+        let err = validate_transmission(&RadioTransmissionRequest {
+            r#loop: true,
+            ..transmission()
+        })
+        .unwrap_err();
+        assert_eq!(err, "looping transmissions require a name to stop them");
+    }
+
+    #[test]
+    fn test_validate_drawing() {
+        let position = InputPosition::default();
+        validate_drawing(
+            &[("from", Some(&position)), ("to", Some(&position))],
+            Some(&color(1.0)),
+            None,
+        )
+        .unwrap();
+        validate_drawing(
+            &[("center", Some(&position))],
+            Some(&color(1.0)),
+            Some(&color(0.0)),
+        )
+        .unwrap();
+
+        let err = validate_drawing(
+            &[("from", Some(&position)), ("to", None)],
+            Some(&color(1.0)),
+            None,
+        )
+        .unwrap_err();
+        assert_eq!(err, "to must be specified");
+
+        let err = validate_drawing(&[("center", Some(&position))], None, None).unwrap_err();
+        assert_eq!(err, "color must be specified");
+
+        let err = validate_drawing(
+            &[("center", Some(&position))],
+            Some(&color(1.0)),
+            Some(&color(2.0)),
+        )
+        .unwrap_err();
+        assert_eq!(err, "fill_color components must be between 0 and 1");
+    }
+
+    #[test]
+    fn test_validate_update() {
+        validate_update(&UpdateDrawingRequest::default()).unwrap();
+        validate_update(&UpdateDrawingRequest {
+            color: Some(color(0.5)),
+            radius: Some(1000.0),
+            text: Some("Target".to_string()),
+            font_size: Some(12),
+            ..Default::default()
+        })
+        .unwrap();
+
+        let invalid = [
+            UpdateDrawingRequest {
+                color: Some(color(-0.1)),
+                ..Default::default()
+            },
+            UpdateDrawingRequest {
+                fill_color: Some(color(f64::NAN)),
+                ..Default::default()
+            },
+            UpdateDrawingRequest {
+                radius: Some(0.0),
+                ..Default::default()
+            },
+            UpdateDrawingRequest {
+                text: Some(String::new()),
+                ..Default::default()
+            },
+            UpdateDrawingRequest {
+                font_size: Some(0),
+                ..Default::default()
+            },
+        ];
+        for request in invalid {
+            assert!(validate_update(&request).is_err(), "{request:?}");
+        }
+    }
+
+    #[test]
+    fn test_validate_color() {
+        validate_color("color", &color(0.0)).unwrap();
+        validate_color("color", &color(1.0)).unwrap();
+        assert!(validate_color("color", &color(1.01)).is_err());
+        assert!(validate_color("color", &color(f64::NAN)).is_err());
+        assert!(
+            validate_color(
+                "color",
+                &Color {
+                    red: 255.0,
+                    ..color(1.0)
+                }
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_validate_text() {
+        validate_text("Target", 12).unwrap();
+        assert_eq!(validate_text("", 12).unwrap_err(), "text must not be empty");
+        assert_eq!(
+            validate_text("Target", 0).unwrap_err(),
+            "font_size must be greater than 0"
+        );
+    }
+
+    #[test]
+    fn test_validate_radius() {
+        validate_radius(0.5).unwrap();
+        assert!(validate_radius(0.0).is_err());
+        assert!(validate_radius(-1.0).is_err());
+        assert!(validate_radius(f64::NAN).is_err());
+    }
 }
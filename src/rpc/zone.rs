@@ -0,0 +1,24 @@
+use stubs::zone::v0::zone_service_server::ZoneService;
+use stubs::*;
+use tonic::{Request, Response, Status};
+
+use super::MissionRpc;
+
+#[tonic::async_trait]
+impl ZoneService for MissionRpc {
+    async fn get_zones(
+        &self,
+        request: Request<zone::v0::GetZonesRequest>,
+    ) -> Result<Response<zone::v0::GetZonesResponse>, Status> {
+        let res = self.request("getZones", request).await?;
+        Ok(Response::new(res))
+    }
+
+    async fn get_zone(
+        &self,
+        request: Request<zone::v0::GetZoneRequest>,
+    ) -> Result<Response<zone::v0::GetZoneResponse>, Status> {
+        let res = self.request("getZone", request).await?;
+        Ok(Response::new(res))
+    }
+}
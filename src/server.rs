@@ -1,39 +1,56 @@
 use std::future::Future;
 use std::net::SocketAddr;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
 
 use dcs_module_ipc::IPC;
 use futures_util::FutureExt;
 use stubs::atmosphere::v0::atmosphere_service_server::AtmosphereServiceServer;
+use stubs::ban::v0::ban_service_server::BanServiceServer;
+use stubs::chat::v0::chat_command_service_server::ChatCommandServiceServer;
 use stubs::coalition::v0::coalition_service_server::CoalitionServiceServer;
 use stubs::controller::v0::controller_service_server::ControllerServiceServer;
 use stubs::custom::v0::custom_service_server::CustomServiceServer;
+use stubs::gatekeeper::v0::gatekeeper_service_server::GatekeeperServiceServer;
 use stubs::group::v0::group_service_server::GroupServiceServer;
 use stubs::hook::v0::hook_service_server::HookServiceServer;
+use stubs::land::v0::land_service_server::LandServiceServer;
 use stubs::metadata::v0::metadata_service_server::MetadataServiceServer;
 use stubs::mission::v0::StreamEventsResponse;
 use stubs::mission::v0::mission_service_server::MissionServiceServer;
 use stubs::net::v0::net_service_server::NetServiceServer;
+use stubs::player::v0::player_service_server::PlayerServiceServer;
 pub use stubs::srs::v0::TransmitRequest;
 use stubs::srs::v0::srs_service_server::{SrsService, SrsServiceServer};
 use stubs::timer::v0::timer_service_server::TimerServiceServer;
 use stubs::trigger::v0::trigger_service_server::TriggerServiceServer;
 use stubs::unit::v0::unit_service_server::UnitServiceServer;
 use stubs::world::v0::world_service_server::WorldServiceServer;
+use stubs::zone::v0::zone_service_server::ZoneServiceServer;
 use tokio::runtime::{Handle, Runtime};
 use tokio::sync::oneshot::{self, Receiver};
 use tokio::sync::{Mutex, mpsc};
 use tokio::time::sleep;
+use tonic::codegen::http::{HeaderName, HeaderValue, Method};
 use tonic::transport;
 use tonic_middleware::RequestInterceptorLayer;
+use tonic_web::GrpcWebLayer;
+use tower::util::option_layer;
+use tower_http::cors::{AllowOrigin, CorsLayer};
 
 use crate::authentication::AuthInterceptor;
-use crate::config::{AuthConfig, Config, SrsConfig, TtsConfig};
-use crate::rpc::{HookRpc, MissionRpc, Srs};
+use crate::config::{
+    AuthConfig, Config, EventSinkConfig, GatewayConfig, GrpcWebConfig, SrsConfig, StreamsConfig,
+    TtsConfig, WebSocketConfig, WebhookConfig, ZonesConfig,
+};
+use crate::gateway::Gateway;
+use crate::health::Health;
+use crate::rpc::{Bans, ChatCommands, Gatekeeper, HookRpc, MissionRpc, Players, Srs};
 use crate::shutdown::{Shutdown, ShutdownHandle};
 use crate::srs::SrsClients;
 use crate::stats::Stats;
+use crate::websocket::WebSocketBridge;
 
 pub struct Server {
     runtime: Runtime,
@@ -50,10 +67,23 @@ struct ServerState {
     ipc_mission: IPC<StreamEventsResponse>,
     ipc_hook: IPC<()>,
     stats: Stats,
+    health: Health,
     tts_config: TtsConfig,
     srs_config: SrsConfig,
     srs_transmit: Arc<Mutex<mpsc::Receiver<TransmitRequest>>>,
     auth_config: AuthConfig,
+    grpc_web_config: GrpcWebConfig,
+    gateway_config: GatewayConfig,
+    websocket_config: WebSocketConfig,
+    webhooks: Vec<WebhookConfig>,
+    zones_config: ZonesConfig,
+    write_dir: PathBuf,
+    event_sinks: Vec<EventSinkConfig>,
+    streams_config: StreamsConfig,
+    gatekeeper: Gatekeeper,
+    chat_commands: ChatCommands,
+    bans: Bans,
+    players: Players,
 }
 
 impl Server {
@@ -63,6 +93,14 @@ impl Server {
         let runtime = Runtime::new()?;
         let shutdown = Shutdown::new();
         let (tx, rx) = mpsc::channel(128);
+        let stats = Stats::new(shutdown.handle());
+        let bans_config = config.bans.clone().unwrap_or_default();
+        let bans = Bans::load(PathBuf::from(&config.write_dir).join(&bans_config.path));
+        let players_config = config.players.clone().unwrap_or_default();
+        let players = Players::load(
+            PathBuf::from(&config.write_dir).join(&players_config.path),
+            &players_config,
+        );
         Ok(Self {
             runtime,
             after_shutdown: None,
@@ -71,11 +109,27 @@ impl Server {
                 eval_enabled: config.eval_enabled,
                 ipc_mission,
                 ipc_hook,
-                stats: Stats::new(shutdown.handle()),
+                health: Health::new(stats.clone()),
+                stats,
                 tts_config: config.tts.clone().unwrap_or_default(),
                 srs_config: config.srs.clone().unwrap_or_default(),
                 srs_transmit: Arc::new(Mutex::new(rx)),
                 auth_config: config.auth.clone().unwrap_or_default(),
+                grpc_web_config: config.grpc_web.clone().unwrap_or_default(),
+                gateway_config: config.gateway.clone().unwrap_or_default(),
+                websocket_config: config.websocket.clone().unwrap_or_default(),
+                webhooks: config.webhooks.clone(),
+                zones_config: config.zones.clone().unwrap_or_default(),
+                write_dir: PathBuf::from(&config.write_dir),
+                event_sinks: config.event_sinks.clone(),
+                streams_config: config.streams.unwrap_or_default(),
+                gatekeeper: Gatekeeper::new(
+                    config.gatekeeper.clone().unwrap_or_default(),
+                    shutdown.handle(),
+                ),
+                chat_commands: ChatCommands::new(shutdown.handle()),
+                bans,
+                players,
             },
             srs_transmit: tx,
             shutdown,
@@ -101,6 +155,13 @@ impl Server {
         self.runtime
             .spawn(self.state.stats.clone().run_in_background());
 
+        self.runtime.spawn(
+            self.state
+                .players
+                .clone()
+                .run_in_background(self.state.ipc_mission.clone(), self.shutdown.handle()),
+        );
+
         self.runtime.spawn(crate::fps::run_in_background(
             self.state.ipc_mission.clone(),
             self.shutdown.handle().signal(),
@@ -110,6 +171,7 @@ impl Server {
     pub fn stop_blocking(mut self) {
         // graceful shutdown
         self.runtime.block_on(self.shutdown.shutdown());
+        self.runtime.block_on(self.state.players.stop());
         if let Some(after_shutdown) = self.after_shutdown.take() {
             let _ = after_shutdown.send(());
         }
@@ -131,6 +193,22 @@ impl Server {
         &self.state.stats
     }
 
+    pub fn health(&self) -> &Health {
+        &self.state.health
+    }
+
+    pub fn gatekeeper(&self) -> &Gatekeeper {
+        &self.state.gatekeeper
+    }
+
+    pub fn chat_commands(&self) -> &ChatCommands {
+        &self.state.chat_commands
+    }
+
+    pub fn bans(&self) -> &Bans {
+        &self.state.bans
+    }
+
     pub fn block_on<F: Future>(&self, future: F) -> F::Output {
         self.runtime.block_on(future)
     }
@@ -196,7 +274,7 @@ async fn try_run(
     state: ServerState,
     shutdown_signal: ShutdownHandle,
     after_shutdown: &mut Receiver<()>,
-) -> Result<(), transport::Error> {
+) -> Result<(), RunError> {
     log::info!("Staring gRPC Server (on {}) ...", state.addr);
 
     let ServerState {
@@ -205,20 +283,38 @@ async fn try_run(
         ipc_mission,
         ipc_hook,
         stats,
+        health,
         tts_config,
         srs_config,
         srs_transmit,
         auth_config,
+        grpc_web_config,
+        gateway_config,
+        websocket_config,
+        webhooks,
+        zones_config,
+        write_dir,
+        event_sinks,
+        streams_config,
+        gatekeeper,
+        chat_commands,
+        bans,
+        players,
     } = state;
 
-    let mut mission_rpc: MissionRpc =
-        MissionRpc::new(ipc_mission.clone(), stats.clone(), shutdown_signal.clone());
+    let mut mission_rpc: MissionRpc = MissionRpc::new(
+        ipc_mission.clone(),
+        stats.clone(),
+        health.clone(),
+        shutdown_signal.clone(),
+    );
     let mut hook_rpc = HookRpc::new(ipc_hook, stats, shutdown_signal.clone());
 
     if eval_enabled {
         mission_rpc.enable_eval();
         hook_rpc.enable_eval();
     }
+    mission_rpc.set_streams_config(streams_config);
 
     let srs_clients = SrsClients::default();
     runtime.spawn(crate::srs::run_in_background(
@@ -228,6 +324,32 @@ async fn try_run(
         shutdown_signal.clone(),
     ));
 
+    if !webhooks.is_empty() {
+        log::info!("Webhooks configured: {}", webhooks.len());
+        runtime.spawn(crate::webhook::run_in_background(
+            mission_rpc.clone(),
+            webhooks,
+            shutdown_signal.clone(),
+        ));
+    }
+
+    if !zones_config.monitors.is_empty() {
+        log::info!("Zone monitors configured: {}", zones_config.monitors.len());
+        runtime.spawn(crate::zone::run_in_background(
+            mission_rpc.clone(),
+            zones_config,
+            shutdown_signal.clone(),
+        ));
+    }
+
+    if !event_sinks.is_empty() {
+        let rpc = mission_rpc.clone();
+        let shutdown_signal = shutdown_signal.clone();
+        runtime.spawn(async move {
+            crate::sink::run_in_background(rpc, &write_dir, event_sinks, shutdown_signal).await
+        });
+    }
+
     let srs = Srs::new(
         tts_config.clone(),
         srs_config.clone(),
@@ -253,18 +375,91 @@ async fn try_run(
     };
 
     log::info!("Authentication enabled: {}", auth_config.enabled);
+    log::info!("gRPC-Web enabled: {}", grpc_web_config.enabled);
+
+    if gateway_config.enabled {
+        let gateway = Gateway {
+            mission: mission_rpc.clone(),
+            hook: hook_rpc.clone(),
+            srs: Srs::new(
+                tts_config.clone(),
+                srs_config.clone(),
+                mission_rpc.clone(),
+                srs_clients.clone(),
+                shutdown_signal.clone(),
+            ),
+            bans: bans.clone(),
+            players: players.clone(),
+            auth: auth_interceptor.clone(),
+        };
+        let addr = SocketAddr::new(addr.ip(), gateway_config.port);
+        let shutdown_signal = shutdown_signal.clone();
+        runtime.spawn(async move {
+            if let Err(err) = crate::gateway::run(gateway, addr, shutdown_signal).await {
+                log::error!("HTTP/JSON gateway failed: {}", err);
+            }
+        });
+    }
+
+    if websocket_config.enabled {
+        let bridge = WebSocketBridge {
+            rpc: mission_rpc.clone(),
+            auth: auth_interceptor.clone(),
+            shutdown_signal: shutdown_signal.clone(),
+        };
+        let addr = SocketAddr::new(addr.ip(), websocket_config.port);
+        runtime.spawn(async move {
+            if let Err(err) = crate::websocket::run(bridge, addr).await {
+                log::error!("WebSocket bridge failed: {}", err);
+            }
+        });
+    }
+
+    let reflection = tonic_reflection::server::Builder::configure()
+        .register_encoded_file_descriptor_set(stubs::FILE_DESCRIPTOR_SET)
+        .build_v1()?;
+    let reflection_v1alpha = tonic_reflection::server::Builder::configure()
+        .register_encoded_file_descriptor_set(stubs::FILE_DESCRIPTOR_SET)
+        .build_v1alpha()?;
+
+    let (health_reporter, health_service) = tonic_health::server::health_reporter();
+    runtime.spawn(crate::health::run_in_background(
+        health,
+        mission_rpc.clone(),
+        hook_rpc.clone(),
+        health_reporter,
+        shutdown_signal.signal(),
+    ));
 
     transport::Server::builder()
+        // gRPC-Web clients are using HTTP/1.1
+        .accept_http1(grpc_web_config.enabled)
+        .layer(option_layer(
+            grpc_web_config
+                .enabled
+                .then(|| cors_layer(&grpc_web_config)),
+        ))
+        .layer(option_layer(
+            grpc_web_config.enabled.then(GrpcWebLayer::new),
+        ))
         .layer(RequestInterceptorLayer::new(auth_interceptor.clone()))
         .add_service(AtmosphereServiceServer::new(mission_rpc.clone()))
+        .add_service(BanServiceServer::new(bans))
+        .add_service(ChatCommandServiceServer::new(chat_commands))
         .add_service(CoalitionServiceServer::new(mission_rpc.clone()))
         .add_service(ControllerServiceServer::new(mission_rpc.clone()))
         .add_service(CustomServiceServer::new(mission_rpc.clone()))
+        .add_service(GatekeeperServiceServer::new(gatekeeper))
         .add_service(GroupServiceServer::new(mission_rpc.clone()))
+        .add_service(health_service)
         .add_service(HookServiceServer::new(hook_rpc))
+        .add_service(LandServiceServer::new(mission_rpc.clone()))
         .add_service(MetadataServiceServer::new(mission_rpc.clone()))
         .add_service(MissionServiceServer::new(mission_rpc.clone()))
         .add_service(NetServiceServer::new(mission_rpc.clone()))
+        .add_service(PlayerServiceServer::new(players))
+        .add_service(reflection)
+        .add_service(reflection_v1alpha)
         .add_service(TimerServiceServer::new(mission_rpc.clone()))
         .add_service(TriggerServiceServer::new(mission_rpc.clone()))
         .add_service(SrsServiceServer::new(Srs::new(
@@ -275,7 +470,8 @@ async fn try_run(
             shutdown_signal.clone(),
         )))
         .add_service(UnitServiceServer::new(mission_rpc.clone()))
-        .add_service(WorldServiceServer::new(mission_rpc))
+        .add_service(WorldServiceServer::new(mission_rpc.clone()))
+        .add_service(ZoneServiceServer::new(mission_rpc))
         .serve_with_shutdown(addr, after_shutdown.map(|_| ()))
         .await?;
 
@@ -284,6 +480,45 @@ async fn try_run(
     Ok(())
 }
 
+/// CORS configuration necessary for browsers to be allowed to send gRPC-Web requests.
+fn cors_layer(config: &GrpcWebConfig) -> CorsLayer {
+    let allow_origin = if config.cors_origins.is_empty() {
+        AllowOrigin::any()
+    } else {
+        AllowOrigin::list(config.cors_origins.iter().filter_map(|origin| {
+            HeaderValue::from_str(origin)
+                .inspect_err(|err| log::warn!("Ignoring invalid CORS origin `{origin}`: {err}"))
+                .ok()
+        }))
+    };
+
+    CorsLayer::new()
+        .allow_origin(allow_origin)
+        .allow_methods([Method::POST, Method::OPTIONS])
+        .allow_headers([
+            HeaderName::from_static("authorization"),
+            HeaderName::from_static("content-type"),
+            HeaderName::from_static("grpc-timeout"),
+            HeaderName::from_static("x-api-key"),
+            HeaderName::from_static("x-grpc-web"),
+            HeaderName::from_static("x-user-agent"),
+        ])
+        .expose_headers([
+            HeaderName::from_static("grpc-status"),
+            HeaderName::from_static("grpc-message"),
+            HeaderName::from_static("grpc-status-details-bin"),
+        ])
+        .max_age(Duration::from_secs(24 * 60 * 60))
+}
+
+#[derive(Debug, thiserror::Error)]
+enum RunError {
+    #[error(transparent)]
+    Transport(#[from] transport::Error),
+    #[error(transparent)]
+    Reflection(#[from] tonic_reflection::server::Error),
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum StartError {
     #[error(transparent)]
@@ -0,0 +1,118 @@
+use std::path::Path;
+use std::sync::Arc;
+
+use futures_util::StreamExt;
+use stubs::mission::v0::StreamEventsResponse;
+use tokio::sync::mpsc;
+use tonic::async_trait;
+
+pub use self::file::FileSink;
+pub use self::tcp::TcpSink;
+pub use self::udp::UdpSink;
+use crate::config::EventSinkConfig;
+use crate::rpc::MissionRpc;
+use crate::shutdown::ShutdownHandle;
+
+mod file;
+mod tcp;
+mod udp;
+
+/// A destination mission events are pushed to (besides gRPC subscribers).
+#[async_trait]
+pub trait EventSink: Send {
+    /// A short description of the sink used for logging.
+    fn name(&self) -> String;
+
+    async fn write(&mut self, event: &StreamEventsResponse) -> Result<(), std::io::Error>;
+}
+
+/// Subscribes to the mission events and forwards them to all configured sinks. Each sink is fed by
+/// its own worker through its own bounded buffer. Events are dropped for a sink whose buffer is
+/// full, so that a slow sink never delays the other sinks or the event producer.
+pub async fn run_in_background(
+    rpc: MissionRpc,
+    write_dir: &Path,
+    configs: Vec<EventSinkConfig>,
+    shutdown_signal: ShutdownHandle,
+) {
+    let mut workers = Vec::with_capacity(configs.len());
+    for config in configs {
+        let (sink, buffer): (Box<dyn EventSink>, usize) = match config {
+            EventSinkConfig::File(config) => (
+                Box::new(FileSink::new(
+                    write_dir.join(&config.path),
+                    config.max_size,
+                    config.max_files,
+                )),
+                config.buffer,
+            ),
+            EventSinkConfig::Tcp(config) => (Box::new(TcpSink::new(config.addr)), config.buffer),
+            EventSinkConfig::Udp(config) => (
+                Box::new(UdpSink::new(config.addr, config.format)),
+                config.buffer,
+            ),
+        };
+
+        log::info!("Writing events to {}", sink.name());
+        let (tx, rx) = mpsc::channel(buffer.max(1));
+        tokio::spawn(write_all(sink, rx));
+        workers.push(Worker { tx, dropped: 0 });
+    }
+
+    let events = rpc.events().await;
+    let mut events = std::pin::pin!(events);
+    let mut shutdown_signal = shutdown_signal.signal();
+
+    loop {
+        // wait for either the shutdown signal or the next event, whatever happens first
+        let event = tokio::select! {
+            _ = &mut shutdown_signal => {
+                break
+            }
+            event = events.next() => event
+        };
+        let Some(event) = event else {
+            break;
+        };
+
+        let event = Arc::new(event);
+        for worker in &mut workers {
+            worker.send(event.clone());
+        }
+    }
+}
+
+struct Worker {
+    tx: mpsc::Sender<Arc<StreamEventsResponse>>,
+    /// Number of events dropped since the buffer was last able to accept an event.
+    dropped: usize,
+}
+
+impl Worker {
+    fn send(&mut self, event: Arc<StreamEventsResponse>) {
+        match self.tx.try_send(event) {
+            Ok(()) => {
+                if self.dropped > 0 {
+                    log::warn!(
+                        "Event sink dropped {} events (buffer was full)",
+                        self.dropped
+                    );
+                    self.dropped = 0;
+                }
+            }
+            Err(mpsc::error::TrySendError::Full(_)) => self.dropped += 1,
+            Err(mpsc::error::TrySendError::Closed(_)) => {}
+        }
+    }
+}
+
+async fn write_all(
+    mut sink: Box<dyn EventSink>,
+    mut rx: mpsc::Receiver<Arc<StreamEventsResponse>>,
+) {
+    while let Some(event) = rx.recv().await {
+        if let Err(err) = sink.write(&event).await {
+            log::warn!("Failed to write event to {}: {}", sink.name(), err);
+        }
+    }
+}
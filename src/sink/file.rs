@@ -0,0 +1,237 @@
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use stubs::mission::v0::StreamEventsResponse;
+use tokio::fs::{self, File, OpenOptions};
+use tokio::io::AsyncWriteExt;
+use tonic::async_trait;
+
+use super::EventSink;
+
+/// Writes events as newline-delimited JSON into a file, which is rotated once it exceeds
+/// `max_size` bytes. Rotated files are suffixed with `.1` (most recent) up to `.{max_files}`.
+pub struct FileSink {
+    path: PathBuf,
+    max_size: u64,
+    max_files: u32,
+    file: Option<File>,
+    size: u64,
+}
+
+impl FileSink {
+    pub fn new(path: PathBuf, max_size: u64, max_files: u32) -> Self {
+        Self {
+            path,
+            max_size,
+            max_files,
+            file: None,
+            size: 0,
+        }
+    }
+
+    async fn rotate(&mut self) -> Result<(), std::io::Error> {
+        self.file = None;
+
+        if self.max_files == 0 {
+            return remove_if_exists(&self.path).await;
+        }
+
+        remove_if_exists(&rotated_path(&self.path, self.max_files)).await?;
+        for i in (1..self.max_files).rev() {
+            rename_if_exists(
+                &rotated_path(&self.path, i),
+                &rotated_path(&self.path, i + 1),
+            )
+            .await?;
+        }
+        rename_if_exists(&self.path, &rotated_path(&self.path, 1)).await
+    }
+}
+
+#[async_trait]
+impl EventSink for FileSink {
+    fn name(&self) -> String {
+        format!("file `{}`", self.path.display())
+    }
+
+    async fn write(&mut self, event: &StreamEventsResponse) -> Result<(), std::io::Error> {
+        let mut line = serde_json::to_vec(event)?;
+        line.push(b'\n');
+
+        if self.file.is_some() && self.size + line.len() as u64 > self.max_size {
+            self.rotate().await?;
+        }
+
+        // the file is only put back after a successful write, so that it is re-opened on the next
+        // write otherwise
+        let mut file = match self.file.take() {
+            Some(file) => file,
+            None => {
+                if let Some(dir) = self.path.parent() {
+                    fs::create_dir_all(dir).await?;
+                }
+                let file = OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(&self.path)
+                    .await?;
+                self.size = file.metadata().await?.len();
+                file
+            }
+        };
+
+        file.write_all(&line).await?;
+        file.flush().await?;
+        self.size += line.len() as u64;
+        self.file = Some(file);
+
+        Ok(())
+    }
+}
+
+fn rotated_path(path: &Path, n: u32) -> PathBuf {
+    let mut path = path.as_os_str().to_owned();
+    path.push(format!(".{n}"));
+    PathBuf::from(path)
+}
+
+async fn remove_if_exists(path: &Path) -> Result<(), std::io::Error> {
+    match fs::remove_file(path).await {
+        Err(err) if err.kind() != ErrorKind::NotFound => Err(err),
+        _ => Ok(()),
+    }
+}
+
+async fn rename_if_exists(from: &Path, to: &Path) -> Result<(), std::io::Error> {
+    match fs::rename(from, to).await {
+        Err(err) if err.kind() != ErrorKind::NotFound => Err(err),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use stubs::mission::v0::StreamEventsResponse;
+
+    use super::*;
+
+    fn event(time: f64) -> StreamEventsResponse {
+        StreamEventsResponse { time, event: None }
+    }
+
+    fn line(time: f64) -> String {
+        format!("{}\n", serde_json::to_string(&event(time)).unwrap())
+    }
+
+    /// A fresh directory per test, as tests run in parallel.
+    async fn test_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "dcs-grpc-file-sink-{}-{}",
+            std::process::id(),
+            name
+        ));
+        let _ = fs::remove_dir_all(&dir).await;
+        dir
+    }
+
+    async fn read(path: &Path) -> Option<String> {
+        fs::read_to_string(path).await.ok()
+    }
+
+    #[tokio::test]
+    async fn test_write_appends_lines() {
+        let dir = test_dir("append").await;
+        let path = dir.join("events.jsonl");
+
+        let mut sink = FileSink::new(path.clone(), 1024, 2);
+        sink.write(&event(1.0)).await.unwrap();
+        sink.write(&event(2.0)).await.unwrap();
+        drop(sink);
+
+        // a new sink appends to the existing file and picks up its size
+        let mut sink = FileSink::new(path.clone(), 1024, 2);
+        sink.write(&event(3.0)).await.unwrap();
+        assert_eq!(
+            sink.size,
+            (line(1.0) + &line(2.0) + &line(3.0)).len() as u64
+        );
+
+        assert_eq!(
+            read(&path).await.unwrap(),
+            line(1.0) + &line(2.0) + &line(3.0)
+        );
+        assert_eq!(read(&rotated_path(&path, 1)).await, None);
+
+        fs::remove_dir_all(&dir).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_rotate_on_max_size() {
+        let dir = test_dir("rotate").await;
+        let path = dir.join("events.jsonl");
+
+        // room for exactly two lines per file
+        let max_size = 2 * line(1.0).len() as u64;
+        let mut sink = FileSink::new(path.clone(), max_size, 2);
+        for i in 1..=7 {
+            sink.write(&event(f64::from(i))).await.unwrap();
+        }
+
+        assert_eq!(read(&path).await.unwrap(), line(7.0));
+        assert_eq!(
+            read(&rotated_path(&path, 1)).await.unwrap(),
+            line(5.0) + &line(6.0)
+        );
+        assert_eq!(
+            read(&rotated_path(&path, 2)).await.unwrap(),
+            line(3.0) + &line(4.0)
+        );
+        // older files beyond `max_files` are removed
+        assert_eq!(read(&rotated_path(&path, 3)).await, None);
+
+        fs::remove_dir_all(&dir).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_rotate_without_rotated_files() {
+        let dir = test_dir("truncate").await;
+        let path = dir.join("events.jsonl");
+
+        let max_size = line(1.0).len() as u64;
+        let mut sink = FileSink::new(path.clone(), max_size, 0);
+        for i in 1..=3 {
+            sink.write(&event(f64::from(i))).await.unwrap();
+        }
+
+        assert_eq!(read(&path).await.unwrap(), line(3.0));
+        assert_eq!(read(&rotated_path(&path, 1)).await, None);
+
+        fs::remove_dir_all(&dir).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_line_larger_than_max_size() {
+        let dir = test_dir("oversized").await;
+        let path = dir.join("events.jsonl");
+
+        // a line that exceeds the limit on its own is still written into a file of its own
+        let mut sink = FileSink::new(path.clone(), 1, 1);
+        sink.write(&event(1.0)).await.unwrap();
+        sink.write(&event(2.0)).await.unwrap();
+
+        assert_eq!(read(&path).await.unwrap(), line(2.0));
+        assert_eq!(read(&rotated_path(&path, 1)).await.unwrap(), line(1.0));
+
+        fs::remove_dir_all(&dir).await.unwrap();
+    }
+
+    #[test]
+    fn test_rotated_path() {
+        assert_eq!(
+            rotated_path(Path::new("Logs/events.jsonl"), 3),
+            PathBuf::from("Logs/events.jsonl.3")
+        );
+    }
+}
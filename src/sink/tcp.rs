@@ -0,0 +1,135 @@
+use std::net::SocketAddr;
+
+use stubs::mission::v0::StreamEventsResponse;
+use tokio::io::AsyncWriteExt;
+use tokio::net::TcpStream;
+use tonic::async_trait;
+
+use super::EventSink;
+
+/// Writes events as newline-delimited JSON to a TCP connection. The connection is established
+/// lazily and re-established on the next event after it failed.
+pub struct TcpSink {
+    addr: SocketAddr,
+    stream: Option<TcpStream>,
+}
+
+impl TcpSink {
+    pub fn new(addr: SocketAddr) -> Self {
+        Self { addr, stream: None }
+    }
+}
+
+#[async_trait]
+impl EventSink for TcpSink {
+    fn name(&self) -> String {
+        format!("tcp://{}", self.addr)
+    }
+
+    async fn write(&mut self, event: &StreamEventsResponse) -> Result<(), std::io::Error> {
+        let mut line = serde_json::to_vec(event)?;
+        line.push(b'\n');
+
+        // the stream is only put back after a successful write, so that it reconnects on the next
+        // write otherwise
+        let mut stream = match self.stream.take() {
+            Some(stream) => stream,
+            None => TcpStream::connect(self.addr).await?,
+        };
+
+        stream.write_all(&line).await?;
+        self.stream = Some(stream);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use tokio::io::{AsyncBufReadExt, BufReader};
+    use tokio::net::TcpListener;
+    use tokio::time::timeout;
+
+    use super::*;
+
+    fn event(time: f64) -> StreamEventsResponse {
+        StreamEventsResponse { time, event: None }
+    }
+
+    async fn read_line(reader: &mut BufReader<TcpStream>) -> String {
+        let mut line = String::new();
+        timeout(Duration::from_secs(5), reader.read_line(&mut line))
+            .await
+            .unwrap()
+            .unwrap();
+        line
+    }
+
+    #[tokio::test]
+    async fn test_write_lines() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let mut sink = TcpSink::new(listener.local_addr().unwrap());
+
+        sink.write(&event(1.0)).await.unwrap();
+        sink.write(&event(2.0)).await.unwrap();
+
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut reader = BufReader::new(stream);
+        for time in [1.0, 2.0] {
+            let line = read_line(&mut reader).await;
+            assert_eq!(
+                line,
+                format!("{}\n", serde_json::to_string(&event(time)).unwrap())
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_connect_error() {
+        // bind and immediately release a port, so that nothing is listening on it
+        let addr = TcpListener::bind("127.0.0.1:0")
+            .await
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        let mut sink = TcpSink::new(addr);
+
+        assert!(sink.write(&event(1.0)).await.is_err());
+        assert!(sink.stream.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_reconnect_after_connection_loss() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let mut sink = TcpSink::new(listener.local_addr().unwrap());
+
+        sink.write(&event(1.0)).await.unwrap();
+        let (stream, _) = listener.accept().await.unwrap();
+        drop(stream);
+
+        // writes into the closed connection only start failing once the peer has reset it
+        let mut failed = false;
+        for _ in 0..100 {
+            if sink.write(&event(2.0)).await.is_err() {
+                failed = true;
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert!(failed, "write into closed connection did not fail");
+        assert!(sink.stream.is_none());
+
+        sink.write(&event(3.0)).await.unwrap();
+        let (stream, _) = timeout(Duration::from_secs(5), listener.accept())
+            .await
+            .unwrap()
+            .unwrap();
+        let line = read_line(&mut BufReader::new(stream)).await;
+        assert_eq!(
+            line,
+            format!("{}\n", serde_json::to_string(&event(3.0)).unwrap())
+        );
+    }
+}
@@ -0,0 +1,124 @@
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr};
+
+use stubs::mission::v0::StreamEventsResponse;
+use time::OffsetDateTime;
+use time::format_description::well_known::Rfc3339;
+use tokio::net::UdpSocket;
+use tonic::async_trait;
+
+use super::EventSink;
+use crate::config::UdpSinkFormat;
+
+/// Sends each event as a single UDP datagram, either as plain JSON or as JSON wrapped into a
+/// syslog message (RFC 5424).
+pub struct UdpSink {
+    addr: SocketAddr,
+    format: UdpSinkFormat,
+    socket: Option<UdpSocket>,
+}
+
+/// Syslog priority of the messages: facility `local0` (16) and severity `informational` (6).
+const SYSLOG_PRIORITY: u8 = 16 * 8 + 6;
+
+impl UdpSink {
+    pub fn new(addr: SocketAddr, format: UdpSinkFormat) -> Self {
+        Self {
+            addr,
+            format,
+            socket: None,
+        }
+    }
+}
+
+#[async_trait]
+impl EventSink for UdpSink {
+    fn name(&self) -> String {
+        format!("udp://{} ({:?})", self.addr, self.format)
+    }
+
+    async fn write(&mut self, event: &StreamEventsResponse) -> Result<(), std::io::Error> {
+        let json = serde_json::to_string(event)?;
+        let msg = match self.format {
+            UdpSinkFormat::Json => json,
+            UdpSinkFormat::Syslog => {
+                let timestamp = OffsetDateTime::now_utc()
+                    .format(&Rfc3339)
+                    .unwrap_or_else(|_| "-".to_string());
+                format!("<{SYSLOG_PRIORITY}>1 {timestamp} - DCS-gRPC - event - {json}")
+            }
+        };
+
+        let socket = match self.socket.take() {
+            Some(socket) => socket,
+            None => {
+                let bind_addr: SocketAddr = if self.addr.is_ipv4() {
+                    (Ipv4Addr::UNSPECIFIED, 0).into()
+                } else {
+                    (Ipv6Addr::UNSPECIFIED, 0).into()
+                };
+                let socket = UdpSocket::bind(bind_addr).await?;
+                socket.connect(self.addr).await?;
+                socket
+            }
+        };
+
+        socket.send(msg.as_bytes()).await?;
+        self.socket = Some(socket);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use tokio::time::timeout;
+
+    use super::*;
+
+    fn event(time: f64) -> StreamEventsResponse {
+        StreamEventsResponse { time, event: None }
+    }
+
+    async fn recv(socket: &UdpSocket) -> String {
+        let mut buf = vec![0; 64 * 1024];
+        let len = timeout(Duration::from_secs(5), socket.recv(&mut buf))
+            .await
+            .unwrap()
+            .unwrap();
+        String::from_utf8(buf[..len].to_vec()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_write_json() {
+        let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let mut sink = UdpSink::new(socket.local_addr().unwrap(), UdpSinkFormat::Json);
+
+        sink.write(&event(1.0)).await.unwrap();
+        sink.write(&event(2.0)).await.unwrap();
+
+        // one datagram per event
+        for time in [1.0, 2.0] {
+            assert_eq!(
+                recv(&socket).await,
+                serde_json::to_string(&event(time)).unwrap()
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_write_syslog() {
+        let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let mut sink = UdpSink::new(socket.local_addr().unwrap(), UdpSinkFormat::Syslog);
+
+        sink.write(&event(1.0)).await.unwrap();
+
+        let msg = recv(&socket).await;
+        let json = serde_json::to_string(&event(1.0)).unwrap();
+        let rest = msg.strip_prefix("<134>1 ").unwrap();
+        let (timestamp, rest) = rest.split_once(' ').unwrap();
+        assert!(OffsetDateTime::parse(timestamp, &Rfc3339).is_ok());
+        assert_eq!(rest, format!("- DCS-gRPC - event - {json}"));
+    }
+}
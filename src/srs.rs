@@ -86,6 +86,8 @@ async fn run(
     shutdown_handle: ShutdownHandle,
 ) -> Result<(), StreamError> {
     let (_, mut rx) = client.start(addr, shutdown_handle.signal()).await?;
+    let _guard = rpc.health().track_srs_connection();
+
     while let Some(p) = rx.recv().await {
         let Packet::Control(msg) = p? else {
             continue;
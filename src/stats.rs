@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::convert::TryFrom;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU32, AtomicUsize, Ordering};
@@ -23,6 +24,19 @@ struct Inner {
     nanoseconds_waited: AtomicUsize,
     /// Stats collected during an interval necessary to create a report at the end of the interval.
     interval_stats: Arc<Mutex<IntervalStats>>,
+    /// The id given to the next stream subscriber.
+    next_subscriber_id: AtomicUsize,
+    /// Dropped events of all current stream subscribers (by subscriber id).
+    subscribers: std::sync::Mutex<HashMap<usize, SubscriberStats>>,
+}
+
+struct SubscriberStats {
+    /// A description of the subscriber used for logging.
+    name: String,
+    /// Number of events dropped for the subscriber (since the last report).
+    dropped: u64,
+    /// Total number of events dropped for the subscriber.
+    dropped_total: u64,
 }
 
 #[derive(Default)]
@@ -48,6 +62,12 @@ pub struct TrackQueueSizeGuard {
     stats: Arc<Inner>,
 }
 
+/// This guard is used to keep track of the events dropped for a stream subscriber.
+pub struct TrackSubscriberGuard {
+    id: usize,
+    stats: Arc<Inner>,
+}
+
 impl Stats {
     pub fn new(shutdown_signal: ShutdownHandle) -> Self {
         Stats(Arc::new(Inner {
@@ -57,6 +77,8 @@ impl Stats {
             queue_size: AtomicU32::new(0),
             nanoseconds_waited: AtomicUsize::new(0),
             interval_stats: Arc::new(Mutex::new(IntervalStats::default())),
+            next_subscriber_id: AtomicUsize::new(0),
+            subscribers: Default::default(),
         }))
     }
 
@@ -83,6 +105,27 @@ impl Stats {
         }
     }
 
+    pub fn track_subscriber(&self, name: String) -> TrackSubscriberGuard {
+        let id = self.0.next_subscriber_id.fetch_add(1, Ordering::Relaxed);
+        self.0.subscribers.lock().unwrap().insert(
+            id,
+            SubscriberStats {
+                name,
+                dropped: 0,
+                dropped_total: 0,
+            },
+        );
+        TrackSubscriberGuard {
+            id,
+            stats: self.0.clone(),
+        }
+    }
+
+    /// The number of calls currently waiting in the queue.
+    pub fn queue_size(&self) -> u32 {
+        self.0.queue_size.load(Ordering::Relaxed)
+    }
+
     pub async fn run_in_background(self) {
         let mut interval = tokio::time::interval(Duration::from_secs(1));
         interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
@@ -173,6 +216,17 @@ impl Stats {
                     queue_size_average,
                     interval_stats.queue_size_highest
                 );
+                for subscriber in self.0.subscribers.lock().unwrap().values_mut() {
+                    if subscriber.dropped > 0 {
+                        log::warn!(
+                            "Dropped events for {}: {} (total={})",
+                            subscriber.name,
+                            subscriber.dropped,
+                            subscriber.dropped_total
+                        );
+                        subscriber.dropped = 0;
+                    }
+                }
 
                 // reset data for next interval
                 last_logged = Instant::now();
@@ -198,3 +252,25 @@ impl Drop for TrackQueueSizeGuard {
         self.stats.queue_size.fetch_sub(1, Ordering::Relaxed);
     }
 }
+
+impl TrackSubscriberGuard {
+    pub fn track_dropped(&self) {
+        if let Some(subscriber) = self.stats.subscribers.lock().unwrap().get_mut(&self.id) {
+            subscriber.dropped += 1;
+            subscriber.dropped_total += 1;
+        }
+    }
+}
+
+impl Drop for TrackSubscriberGuard {
+    fn drop(&mut self) {
+        let subscriber = self.stats.subscribers.lock().unwrap().remove(&self.id);
+        if let Some(subscriber) = subscriber.filter(|s| s.dropped_total > 0) {
+            log::info!(
+                "Subscriber {} dropped {} events in total",
+                subscriber.name,
+                subscriber.dropped_total
+            );
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
 use std::time::{Duration, Instant};
 
 use futures_util::TryFutureExt;
@@ -9,39 +10,195 @@ use stubs::common;
 use stubs::common::v0::{Coalition, GroupCategory, Orientation, Position, Unit, Vector, Velocity};
 use stubs::group::v0::GetUnitsRequest;
 use stubs::group::v0::group_service_server::GroupService;
-use stubs::mission::v0::stream_events_response::{BirthEvent, DeadEvent, Event};
-use stubs::mission::v0::stream_units_response::{UnitGone, Update};
-use stubs::mission::v0::{StreamUnitsRequest, StreamUnitsResponse};
+use stubs::mission::v0::stream_events_response::{BirthEvent, DeadEvent, Event, LaggedEvent};
+use stubs::mission::v0::stream_units_response::{self, UnitGone, Update};
+use stubs::mission::v0::{StreamEventsResponse, StreamUnitsRequest, StreamUnitsResponse};
 use stubs::unit::v0::unit_service_server::UnitService;
 use stubs::unit::v0::{GetTransformRequest, GetTransformResponse};
 use tokio::sync::mpsc::Sender;
-use tokio::sync::mpsc::error::SendError;
+use tokio::sync::mpsc::error::TrySendError;
 use tokio::time::MissedTickBehavior;
 use tonic::{Code, Request, Status};
 
+use crate::config::StreamsConfig;
 use crate::rpc::MissionRpc;
+use crate::stats::TrackSubscriberGuard;
 
-/// Stream unit updates.
+/// A message of a stream that can tell its subscriber how many messages got dropped for it.
+pub trait Lagged {
+    /// The marker telling that `count` messages got dropped, sent right before `self`.
+    fn lagged(&self, count: u32) -> Self;
+}
+
+impl Lagged for StreamEventsResponse {
+    fn lagged(&self, count: u32) -> Self {
+        StreamEventsResponse {
+            time: self.time,
+            event: Some(Event::Lagged(LaggedEvent { count })),
+        }
+    }
+}
+
+impl Lagged for StreamUnitsResponse {
+    fn lagged(&self, count: u32) -> Self {
+        StreamUnitsResponse {
+            time: self.time,
+            update: Some(Update::Lagged(stream_units_response::Lagged { count })),
+        }
+    }
+}
+
+/// The sending end of the stream to a single subscriber. Messages are put into the bounded buffer
+/// of the subscriber without waiting for it. If the buffer is full, because the subscriber doesn't
+/// keep up with reading its stream, messages are dropped for it. The number of dropped messages is
+/// reported to the subscriber via a [Lagged] marker right before the next message it receives.
+/// Subscribers that dropped more than the configured maximum are disconnected.
+pub struct Subscriber<T> {
+    tx: Sender<Result<T, Status>>,
+    max_dropped: Option<u64>,
+    guard: TrackSubscriberGuard,
+    /// Number of messages dropped since the last [Lagged] marker got through.
+    lagged: AtomicU32,
+    /// Total number of messages dropped for the subscriber.
+    dropped_total: AtomicU64,
+}
+
+/// The subscriber either went away or got disconnected.
+#[derive(Debug, thiserror::Error)]
+#[error("the subscriber is gone")]
+pub struct Closed;
+
+impl<T: Lagged> Subscriber<T> {
+    pub fn new(
+        tx: Sender<Result<T, Status>>,
+        config: &StreamsConfig,
+        guard: TrackSubscriberGuard,
+    ) -> Self {
+        Subscriber {
+            tx,
+            max_dropped: config.max_dropped_events,
+            guard,
+            lagged: AtomicU32::new(0),
+            dropped_total: AtomicU64::new(0),
+        }
+    }
+
+    /// Completes once the subscriber went away.
+    async fn closed(&self) {
+        self.tx.closed().await
+    }
+
+    /// Sends the message without waiting for the subscriber, dropping it if its buffer is full.
+    async fn send(&self, msg: T) -> Result<(), Closed> {
+        let lagged = self.lagged.load(Ordering::Relaxed);
+        let mut result = Ok(());
+        if lagged > 0 {
+            result = self.tx.try_send(Ok(msg.lagged(lagged)));
+            if result.is_ok() {
+                self.lagged.fetch_sub(lagged, Ordering::Relaxed);
+            }
+        }
+        if result.is_ok() {
+            result = self.tx.try_send(Ok(msg));
+        }
+
+        match result {
+            Ok(()) => Ok(()),
+            Err(TrySendError::Full(_)) => {
+                let _ = self
+                    .lagged
+                    .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| {
+                        Some(n.saturating_add(1))
+                    });
+                let dropped_total = self.dropped_total.fetch_add(1, Ordering::Relaxed) + 1;
+                self.guard.track_dropped();
+
+                if self.max_dropped.is_some_and(|max| dropped_total > max) {
+                    log::warn!(
+                        "Disconnecting subscriber after it dropped {} messages",
+                        dropped_total
+                    );
+                    self.fail(Status::resource_exhausted(format!(
+                        "disconnected after dropping {dropped_total} messages for not keeping up \
+                         with reading the stream"
+                    )))
+                    .await;
+                    return Err(Closed);
+                }
+
+                Ok(())
+            }
+            Err(TrySendError::Closed(_)) => Err(Closed),
+        }
+    }
+
+    /// Sends the message, waiting for room in the buffer of the subscriber if necessary.
+    async fn send_wait(&self, msg: T) -> Result<(), Closed> {
+        self.tx.send(Ok(msg)).await.map_err(|_| Closed)
+    }
+
+    /// Sends the error that ends the stream, waiting until either there is room for it in the
+    /// buffer, or the subscriber went away.
+    async fn fail(&self, err: Status) {
+        tokio::select! {
+            _ = self.tx.send(Err(err)) => {},
+            _ = self.tx.closed() => {},
+        }
+    }
+}
+
+/// Forward mission events to a single subscriber, as they come in.
+pub async fn stream_events(
+    events: impl futures_util::Stream<Item = StreamEventsResponse>,
+    subscriber: Subscriber<StreamEventsResponse>,
+) {
+    let mut events = std::pin::pin!(events);
+
+    loop {
+        // wait for either the subscriber to go away or the next event, whatever happens first
+        let event = tokio::select! {
+            _ = subscriber.closed() => break,
+            event = events.next() => event,
+        };
+        let Some(event) = event else {
+            break;
+        };
+
+        if subscriber.send(event).await.is_err() {
+            break;
+        }
+    }
+}
+
+/// Stream unit updates to a single subscriber. A failure of the stream is sent to the subscriber
+/// as final error.
 pub async fn stream_units(
     opts: StreamUnitsRequest,
     rpc: MissionRpc,
-    tx: Sender<Result<StreamUnitsResponse, Status>>,
-) -> Result<(), Error> {
+    subscriber: Subscriber<StreamUnitsResponse>,
+) {
     // initialize the state for the current units stream instance
     let poll_rate = opts.poll_rate.unwrap_or(5);
     let max_backoff = Duration::from_secs(opts.max_backoff.unwrap_or(30).max(poll_rate) as u64);
     let poll_rate = Duration::from_secs(poll_rate as u64);
-    let category = GroupCategory::try_from(opts.category).unwrap_or(GroupCategory::Unspecified);
     let mut state = State {
         units: HashMap::new(),
         ctx: Context {
             rpc,
-            tx,
+            subscriber,
             poll_rate,
             max_backoff,
         },
     };
 
+    if let Err(Error::Status(err)) = sync_units(&opts, &mut state).await {
+        state.ctx.subscriber.fail(err).await;
+    }
+}
+
+async fn sync_units(opts: &StreamUnitsRequest, state: &mut State) -> Result<(), Error> {
+    let category = GroupCategory::try_from(opts.category).unwrap_or(GroupCategory::Unspecified);
+
     // initial full-sync of all current units inside of the mission
     let groups = futures_util::future::try_join_all(
         [Coalition::Blue, Coalition::Red, Coalition::Neutral].map(|coalition| {
@@ -79,15 +236,16 @@ pub async fn stream_units(
         )
     }
 
-    // send out all initial units
+    // send out all initial units (waiting for the subscriber, as they could easily exceed its
+    // buffer)
     for unit_state in state.units.values() {
         state
             .ctx
-            .tx
-            .send(Ok(StreamUnitsResponse {
+            .subscriber
+            .send_wait(StreamUnitsResponse {
                 time: unit_state.update_time,
                 update: Some(Update::Unit(unit_state.unit.clone())),
-            }))
+            })
             .await?;
     }
 
@@ -95,7 +253,7 @@ pub async fn stream_units(
     let mut events = state.ctx.rpc.events().await;
 
     // create an interval used to poll the mission for updates
-    let mut interval = tokio::time::interval(poll_rate);
+    let mut interval = tokio::time::interval(state.ctx.poll_rate);
     interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
 
     loop {
@@ -105,12 +263,12 @@ pub async fn stream_units(
             Some(stubs::mission::v0::StreamEventsResponse { time, event: Some(event), .. })
                 = events.next() =>
             {
-                handle_event(&mut state, time, event, category).await?;
+                handle_event(state, time, event, category).await?;
             }
 
             // poll units for updates
             _ = interval.tick() => {
-                update_units(&mut state).await?;
+                update_units(state).await?;
             }
         }
     }
@@ -126,7 +284,7 @@ struct State {
 /// Various structs and options used to handle unit updates.
 struct Context {
     rpc: MissionRpc,
-    tx: Sender<Result<StreamUnitsResponse, Status>>,
+    subscriber: Subscriber<StreamUnitsResponse>,
     poll_rate: Duration,
     max_backoff: Duration,
 }
@@ -156,11 +314,11 @@ async fn handle_event(
             if category == unit_category || category == GroupCategory::Unspecified {
                 state
                     .ctx
-                    .tx
-                    .send(Ok(StreamUnitsResponse {
+                    .subscriber
+                    .send(StreamUnitsResponse {
                         time,
                         update: Some(Update::Unit(unit.clone())),
-                    }))
+                    })
                     .await?;
                 state.units.insert(unit.name.clone(), UnitState::new(unit));
             }
@@ -178,14 +336,14 @@ async fn handle_event(
             if let Some(unit_state) = state.units.remove(&name) {
                 state
                     .ctx
-                    .tx
-                    .send(Ok(StreamUnitsResponse {
+                    .subscriber
+                    .send(StreamUnitsResponse {
                         time,
                         update: Some(Update::Gone(UnitGone {
                             id: unit_state.unit.id,
                             name: unit_state.unit.name.clone(),
                         })),
-                    }))
+                    })
                     .await?;
             }
         }
@@ -223,11 +381,11 @@ async fn update_unit(ctx: &Context, unit_state: &mut UnitState) -> Result<(), Er
     match unit_state.update(ctx).await {
         Ok(changed) => {
             if changed {
-                ctx.tx
-                    .send(Ok(StreamUnitsResponse {
+                ctx.subscriber
+                    .send(StreamUnitsResponse {
                         time: unit_state.update_time,
                         update: Some(Update::Unit(unit_state.unit.clone())),
-                    }))
+                    })
                     .await?;
                 unit_state.backoff = Duration::ZERO;
                 unit_state.last_changed = Instant::now();
@@ -239,8 +397,8 @@ async fn update_unit(ctx: &Context, unit_state: &mut UnitState) -> Result<(), Er
         }
         // if the unit was not found, flag it as gone, and continue with the next unit for now
         Err(err) if err.code() == Code::NotFound => {
-            ctx.tx
-                .send(Ok(StreamUnitsResponse {
+            ctx.subscriber
+                .send(StreamUnitsResponse {
                     // The time provided here is just the last time an update was received for the
                     // unit. It is not exactly the time the unit got destroyed. Since this not-found
                     // handling is just a safeguard if a `Dead` event was missed / not fired by DCS,
@@ -250,7 +408,7 @@ async fn update_unit(ctx: &Context, unit_state: &mut UnitState) -> Result<(), Er
                         id: unit_state.unit.id,
                         name: unit_state.unit.name.clone(),
                     })),
-                }))
+                })
                 .await?;
 
             unit_state.is_gone = true;
@@ -349,11 +507,11 @@ impl UnitState {
 
 #[derive(Debug, thiserror::Error)]
 #[allow(clippy::large_enum_variant)]
-pub enum Error {
+enum Error {
     #[error(transparent)]
     Status(#[from] Status),
-    #[error("the channel got closed")]
-    Send(#[from] SendError<Result<StreamUnitsResponse, Status>>),
+    #[error(transparent)]
+    Closed(#[from] Closed),
 }
 
 /// Check whether two positions are equal, taking an epsilon into account.
@@ -456,3 +614,105 @@ fn speed_equalish(a: f64, b: f64) -> bool {
     const EPSILON: f64 = 0.001;
     (a - b).abs() < EPSILON
 }
+
+#[cfg(test)]
+mod tests {
+    use futures_util::stream;
+    use stubs::mission::v0::stream_events_response::MissionStartEvent;
+    use tokio::sync::{mpsc, oneshot};
+
+    use super::*;
+    use crate::shutdown::Shutdown;
+    use crate::stats::Stats;
+
+    fn subscriber(
+        buffer: usize,
+        max_dropped_events: Option<u64>,
+    ) -> (
+        Subscriber<StreamEventsResponse>,
+        mpsc::Receiver<Result<StreamEventsResponse, Status>>,
+    ) {
+        let stats = Stats::new(Shutdown::new().handle());
+        let config = StreamsConfig {
+            event_buffer: buffer,
+            max_dropped_events,
+        };
+        let (tx, rx) = mpsc::channel(buffer);
+        let subscriber = Subscriber::new(tx, &config, stats.track_subscriber("test".to_string()));
+        (subscriber, rx)
+    }
+
+    fn event(time: f64) -> StreamEventsResponse {
+        StreamEventsResponse {
+            time,
+            event: Some(Event::MissionStart(MissionStartEvent {})),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_lagged_event() {
+        let (subscriber, mut rx) = subscriber(2, None);
+        let (blocked_tx, blocked_rx) = oneshot::channel();
+        let (drained_tx, drained_rx) = oneshot::channel();
+        let events =
+            stream::iter((1..=4).map(|time| event(time.into()))).chain(stream::once(async move {
+                // all previous events got handled once the next one is polled
+                blocked_tx.send(()).unwrap();
+                drained_rx.await.unwrap();
+                event(5.0)
+            }));
+        let task = tokio::spawn(stream_events(events, subscriber));
+
+        // the first two events filled the buffer, the other two got dropped
+        blocked_rx.await.unwrap();
+        assert_eq!(rx.recv().await.unwrap().unwrap(), event(1.0));
+        assert_eq!(rx.recv().await.unwrap().unwrap(), event(2.0));
+        drained_tx.send(()).unwrap();
+
+        assert_eq!(
+            rx.recv().await.unwrap().unwrap(),
+            StreamEventsResponse {
+                time: 5.0,
+                event: Some(Event::Lagged(LaggedEvent { count: 2 })),
+            }
+        );
+        assert_eq!(rx.recv().await.unwrap().unwrap(), event(5.0));
+
+        task.await.unwrap();
+        assert!(rx.recv().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_max_dropped_events() {
+        let (subscriber, mut rx) = subscriber(1, Some(1));
+        let (blocked_tx, blocked_rx) = oneshot::channel();
+        let (drained_tx, drained_rx) = oneshot::channel();
+        let events =
+            stream::iter((1..=2).map(|time| event(time.into()))).chain(stream::once(async move {
+                blocked_tx.send(()).unwrap();
+                drained_rx.await.unwrap();
+                event(3.0)
+            }));
+        let task = tokio::spawn(stream_events(events, subscriber));
+
+        // the first event filled the buffer and dropping the second one is still tolerated
+        blocked_rx.await.unwrap();
+        assert_eq!(rx.recv().await.unwrap().unwrap(), event(1.0));
+        drained_tx.send(()).unwrap();
+
+        // the lagged marker fills the buffer again, so that dropping the third event exceeds the
+        // maximum
+        assert_eq!(
+            rx.recv().await.unwrap().unwrap(),
+            StreamEventsResponse {
+                time: 3.0,
+                event: Some(Event::Lagged(LaggedEvent { count: 1 })),
+            }
+        );
+        let err = rx.recv().await.unwrap().unwrap_err();
+        assert_eq!(err.code(), Code::ResourceExhausted);
+
+        task.await.unwrap();
+        assert!(rx.recv().await.is_none());
+    }
+}
@@ -0,0 +1,449 @@
+use std::time::Duration;
+
+use backoff::ExponentialBackoff;
+use futures_util::StreamExt;
+use serde_json::Value;
+use tokio::sync::mpsc;
+
+use crate::config::WebhookConfig;
+use crate::rpc::MissionRpc;
+use crate::shutdown::ShutdownHandle;
+
+/// Subscribes to the mission events and POSTs each event matching the rules of a webhook to its
+/// url. Each webhook is delivered by its own worker with its own bounded queue, so that a slow or
+/// unavailable webhook doesn't delay the other ones.
+pub async fn run_in_background(
+    rpc: MissionRpc,
+    webhooks: Vec<WebhookConfig>,
+    shutdown_signal: ShutdownHandle,
+) {
+    let client = reqwest::Client::new();
+    let workers = webhooks
+        .into_iter()
+        .map(|webhook| {
+            let (tx, rx) = mpsc::channel(64);
+            tokio::spawn(deliver_all(client.clone(), webhook.clone(), rx));
+            (webhook, tx)
+        })
+        .collect::<Vec<_>>();
+
+    let events = rpc.events().await;
+    let mut events = std::pin::pin!(events);
+    let mut shutdown_signal = shutdown_signal.signal();
+
+    loop {
+        // wait for either the shutdown signal or the next event, whatever happens first
+        let event = tokio::select! {
+            _ = &mut shutdown_signal => {
+                break
+            }
+            event = events.next() => event
+        };
+        let Some(event) = event else {
+            break;
+        };
+
+        let event = match serde_json::to_value(&event) {
+            Ok(event) => event,
+            Err(err) => {
+                log::error!("failed to serialize event for webhooks: {}", err);
+                continue;
+            }
+        };
+
+        for (webhook, tx) in &workers {
+            if !matches(webhook, &event) {
+                continue;
+            }
+
+            let payload = match &webhook.template {
+                Some(template) => render(template, &event),
+                None => event.clone(),
+            };
+            if tx.try_send(payload).is_err() {
+                log::warn!(
+                    "Webhook queue for `{}` is full, dropping event",
+                    redact_url(&webhook.url)
+                );
+            }
+        }
+    }
+}
+
+async fn deliver_all(
+    client: reqwest::Client,
+    webhook: WebhookConfig,
+    mut rx: mpsc::Receiver<Value>,
+) {
+    while let Some(payload) = rx.recv().await {
+        if let Err(err) = deliver(&client, &webhook, &payload).await {
+            log::error!(
+                "Failed to deliver webhook `{}`: {}",
+                redact_url(&webhook.url),
+                err
+            );
+        }
+    }
+}
+
+/// POST the payload to the webhook. Server errors, rate limits and connection issues are retried
+/// with an exponential backoff, other client errors are not.
+async fn deliver(
+    client: &reqwest::Client,
+    webhook: &WebhookConfig,
+    payload: &Value,
+) -> Result<(), reqwest::Error> {
+    let backoff = ExponentialBackoff {
+        initial_interval: Duration::from_secs(1),
+        max_interval: Duration::from_secs(30),
+        max_elapsed_time: Some(Duration::from_secs(2 * 60)),
+        ..Default::default()
+    };
+
+    backoff::future::retry_notify(
+        backoff,
+        || async {
+            let mut req = client.post(&webhook.url).json(payload);
+            for (name, value) in &webhook.headers {
+                req = req.header(name, value);
+            }
+            let res = req.send().await.map_err(backoff::Error::transient)?;
+            match res.error_for_status() {
+                Ok(_) => Ok(()),
+                Err(err)
+                    if err.status().is_some_and(|status| {
+                        status.is_client_error() && status.as_u16() != 429
+                    }) =>
+                {
+                    Err(backoff::Error::permanent(err))
+                }
+                Err(err) => Err(backoff::Error::transient(err)),
+            }
+        },
+        |err, backoff: Duration| {
+            log::debug!(
+                "retrying webhook with backoff {:.2}s after error: {err}",
+                backoff.as_secs_f64(),
+            );
+        },
+    )
+    .await
+}
+
+/// Check whether the event (in its JSON representation) matches all rules of the webhook.
+fn matches(webhook: &WebhookConfig, event: &Value) -> bool {
+    let kind = event.pointer("/event/type").and_then(Value::as_str);
+
+    if !webhook.events.is_empty()
+        && !kind.is_some_and(|kind| webhook.events.iter().any(|e| e == kind))
+    {
+        return false;
+    }
+
+    if let Some(coalition) = webhook.coalition {
+        let coalition = i64::from(coalition.to_proto());
+        if !any_field(event, "coalition", &|v| v.as_i64() == Some(coalition)) {
+            return false;
+        }
+    }
+
+    if !webhook.players.is_empty() {
+        let is_player = |v: &Value| {
+            v.as_str()
+                .is_some_and(|v| webhook.players.iter().any(|p| p == v))
+        };
+        let matches_player = any_field(event, "playerName", &is_player)
+            || any_field(event, "ucid", &is_player)
+            || (kind == Some("connect") && event.pointer("/event/name").is_some_and(is_player));
+        if !matches_player {
+            return false;
+        }
+    }
+
+    true
+}
+
+/// Recursively search for a field with the given `key` whose value satisfies `predicate`.
+fn any_field(value: &Value, key: &str, predicate: &dyn Fn(&Value) -> bool) -> bool {
+    match value {
+        Value::Object(obj) => obj
+            .iter()
+            .any(|(k, v)| (k == key && predicate(v)) || any_field(v, key, predicate)),
+        Value::Array(arr) => arr.iter().any(|v| any_field(v, key, predicate)),
+        _ => false,
+    }
+}
+
+/// Render the payload template by replacing all `{{path.to.field}}` placeholders with the
+/// corresponding values of the event. A string consisting of only a single placeholder is
+/// replaced by the value itself (keeping its JSON type), otherwise the value is interpolated into
+/// the string.
+fn render(template: &Value, event: &Value) -> Value {
+    match template {
+        Value::String(s) => render_str(s, event),
+        Value::Array(arr) => Value::Array(arr.iter().map(|v| render(v, event)).collect()),
+        Value::Object(obj) => Value::Object(
+            obj.iter()
+                .map(|(k, v)| (k.clone(), render(v, event)))
+                .collect(),
+        ),
+        v => v.clone(),
+    }
+}
+
+fn render_str(s: &str, event: &Value) -> Value {
+    if let Some(path) = s
+        .trim()
+        .strip_prefix("{{")
+        .and_then(|s| s.strip_suffix("}}"))
+        .filter(|path| !path.contains("{{") && !path.contains("}}"))
+    {
+        return lookup(event, path.trim()).cloned().unwrap_or(Value::Null);
+    }
+
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(start) = rest.find("{{") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            out.push_str(&rest[start..]);
+            rest = "";
+            break;
+        };
+        match lookup(event, after[..end].trim()) {
+            Some(Value::String(v)) => out.push_str(v),
+            Some(Value::Null) | None => {}
+            Some(v) => out.push_str(&v.to_string()),
+        }
+        rest = &after[end + 2..];
+    }
+    out.push_str(rest);
+
+    Value::String(out)
+}
+
+fn lookup<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.').try_fold(value, |value, key| match value {
+        Value::Object(obj) => obj.get(key),
+        Value::Array(arr) => key.parse::<usize>().ok().and_then(|i| arr.get(i)),
+        _ => None,
+    })
+}
+
+/// Webhook urls usually contain secrets (e.g. Discord webhook tokens), so only log their origin.
+fn redact_url(url: &str) -> &str {
+    url.split_once("://")
+        .and_then(|(scheme, rest)| rest.find('/').map(|i| &url[..scheme.len() + 3 + i]))
+        .unwrap_or(url)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+
+    use axum::Router;
+    use axum::extract::State;
+    use axum::http::{HeaderMap, StatusCode};
+    use axum::routing::post;
+    use serde_json::json;
+    use tokio::net::TcpListener;
+
+    use super::*;
+    use crate::config::CoalitionFilter;
+
+    fn webhook(url: &str) -> WebhookConfig {
+        WebhookConfig {
+            url: url.to_string(),
+            events: Vec::new(),
+            coalition: None,
+            players: Vec::new(),
+            headers: HashMap::new(),
+            template: None,
+        }
+    }
+
+    fn kill_event() -> Value {
+        json!({
+            "time": 42.5,
+            "event": {
+                "type": "kill",
+                "initiator": {
+                    "initiator": {
+                        "unit": { "name": "Aerial-1-1", "playerName": "Hawk", "coalition": 3 }
+                    }
+                },
+                "target": {
+                    "initiator": {
+                        "unit": { "name": "Ground-1-1", "coalition": 2 }
+                    }
+                },
+                "weaponName": "AIM_120C"
+            }
+        })
+    }
+
+    #[test]
+    fn test_matches_event_types() {
+        let mut webhook = webhook("http://127.0.0.1/");
+        assert!(matches(&webhook, &kill_event()));
+
+        webhook.events = vec!["kill".to_string(), "missionEnd".to_string()];
+        assert!(matches(&webhook, &kill_event()));
+
+        webhook.events = vec!["missionEnd".to_string()];
+        assert!(!matches(&webhook, &kill_event()));
+    }
+
+    #[test]
+    fn test_matches_coalition() {
+        let mut webhook = webhook("http://127.0.0.1/");
+        webhook.coalition = Some(CoalitionFilter::Red);
+        assert!(matches(&webhook, &kill_event()));
+
+        webhook.coalition = Some(CoalitionFilter::Neutral);
+        assert!(!matches(&webhook, &kill_event()));
+    }
+
+    #[test]
+    fn test_matches_players() {
+        let mut webhook = webhook("http://127.0.0.1/");
+        webhook.players = vec!["Hawk".to_string()];
+        assert!(matches(&webhook, &kill_event()));
+
+        webhook.players = vec!["Viper".to_string()];
+        assert!(!matches(&webhook, &kill_event()));
+
+        webhook.players = vec!["abc123".to_string()];
+        let connect = json!({
+            "time": 1.0,
+            "event": { "type": "connect", "name": "Viper", "ucid": "abc123", "id": 2 }
+        });
+        assert!(matches(&webhook, &connect));
+
+        webhook.players = vec!["Viper".to_string()];
+        assert!(matches(&webhook, &connect));
+    }
+
+    #[test]
+    fn test_render_template() {
+        let template = json!({
+            "content": "{{event.type}} by {{event.initiator.initiator.unit.playerName}} at {{time}}",
+            "time": "{{time}}",
+            "missing": "{{event.nope}}",
+            "embeds": [{ "title": "{{ event.weaponName }}", "fixed": 1 }],
+        });
+        assert_eq!(
+            render(&template, &kill_event()),
+            json!({
+                "content": "kill by Hawk at 42.5",
+                "time": 42.5,
+                "missing": null,
+                "embeds": [{ "title": "AIM_120C", "fixed": 1 }],
+            })
+        );
+    }
+
+    #[test]
+    fn test_render_unclosed_placeholder() {
+        assert_eq!(
+            render_str("{{time}} and {{event.type", &kill_event()),
+            json!("42.5 and {{event.type")
+        );
+    }
+
+    #[test]
+    fn test_redact_url() {
+        assert_eq!(
+            redact_url("https://discord.com/api/webhooks/123/secret"),
+            "https://discord.com"
+        );
+        assert_eq!(redact_url("http://127.0.0.1:8080"), "http://127.0.0.1:8080");
+    }
+
+    /// A local HTTP stand-in that answers with the given status codes (in order, the last one is
+    /// repeated) and records the headers and bodies of all received requests.
+    struct StandIn {
+        url: String,
+        received: Arc<Mutex<Vec<(HeaderMap, Value)>>>,
+    }
+
+    async fn stand_in(statuses: &[u16]) -> StandIn {
+        type Shared = (Arc<Mutex<Vec<(HeaderMap, Value)>>>, Arc<Mutex<Vec<u16>>>);
+
+        async fn handle(
+            State((received, statuses)): State<Shared>,
+            headers: HeaderMap,
+            axum::Json(body): axum::Json<Value>,
+        ) -> StatusCode {
+            received.lock().unwrap().push((headers, body));
+            let mut statuses = statuses.lock().unwrap();
+            let status = if statuses.len() > 1 {
+                statuses.remove(0)
+            } else {
+                statuses[0]
+            };
+            StatusCode::from_u16(status).unwrap()
+        }
+
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let statuses = Arc::new(Mutex::new(statuses.to_vec()));
+        let app = Router::new()
+            .route("/hook", post(handle))
+            .with_state((received.clone(), statuses));
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        StandIn {
+            url: format!("http://{addr}/hook"),
+            received,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_deliver() {
+        let server = stand_in(&[204]).await;
+        let mut webhook = webhook(&server.url);
+        webhook
+            .headers
+            .insert("X-Token".to_string(), "secret".to_string());
+
+        deliver(&reqwest::Client::new(), &webhook, &kill_event())
+            .await
+            .unwrap();
+
+        let received = server.received.lock().unwrap();
+        assert_eq!(received.len(), 1);
+        assert_eq!(received[0].0.get("X-Token").unwrap(), "secret");
+        assert_eq!(received[0].1, kill_event());
+    }
+
+    #[tokio::test]
+    async fn test_deliver_retries_server_errors() {
+        let server = stand_in(&[500, 429, 200]).await;
+        deliver(
+            &reqwest::Client::new(),
+            &webhook(&server.url),
+            &kill_event(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(server.received.lock().unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_deliver_does_not_retry_client_errors() {
+        let server = stand_in(&[400, 200]).await;
+        let err = deliver(
+            &reqwest::Client::new(),
+            &webhook(&server.url),
+            &kill_event(),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status().map(|s| s.as_u16()), Some(400));
+        assert_eq!(server.received.lock().unwrap().len(), 1);
+    }
+}
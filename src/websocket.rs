@@ -0,0 +1,348 @@
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::Router;
+use axum::extract::State;
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::http::HeaderMap;
+use axum::response::Response;
+use axum::routing::get;
+use futures_util::StreamExt;
+use serde::{Deserialize, Serialize};
+use stubs::mission::v0::{StreamEventsResponse, StreamUnitsRequest, StreamUnitsResponse};
+use tokio::net::TcpListener;
+use tokio::sync::mpsc;
+
+use crate::authentication::AuthInterceptor;
+use crate::rpc::MissionRpc;
+use crate::shutdown::ShutdownHandle;
+
+/// The time a client has to send its [Subscription] after connecting.
+const SUBSCRIPTION_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// An optional WebSocket bridge pushing `StreamEventsResponse` and `StreamUnitsResponse` messages
+/// as JSON. After connecting, the client sends a [Subscription] as its first message, which
+/// decides which streams are pushed to it.
+pub struct WebSocketBridge {
+    pub rpc: MissionRpc,
+    pub auth: AuthInterceptor,
+    pub shutdown_signal: ShutdownHandle,
+}
+
+/// The first message a client has to send after connecting.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Subscription {
+    /// The API key, for clients that are not able to set the `X-API-Key` or `Authorization`
+    /// header (like browsers).
+    api_key: Option<String>,
+    /// Subscribe to mission events.
+    events: Option<EventsFilter>,
+    /// Subscribe to unit updates.
+    units: Option<UnitsOptions>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EventsFilter {
+    /// The event types (e.g. `shot` or `missionStart`) to receive. All events are pushed if
+    /// empty.
+    #[serde(default)]
+    types: Vec<String>,
+}
+
+/// Same options as `StreamUnitsRequest`, but with all of them being optional.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UnitsOptions {
+    poll_rate: Option<u32>,
+    max_backoff: Option<u32>,
+    #[serde(default)]
+    category: i32,
+}
+
+/// A message pushed to the client.
+#[derive(Serialize)]
+#[serde(tag = "stream", content = "message", rename_all = "camelCase")]
+enum Push<'a> {
+    Events(&'a StreamEventsResponse),
+    Units(&'a StreamUnitsResponse),
+    Error(&'a str),
+}
+
+pub async fn run(bridge: WebSocketBridge, addr: SocketAddr) -> Result<(), std::io::Error> {
+    log::info!("Staring WebSocket bridge (on {}) ...", addr);
+
+    let shutdown_signal = bridge.shutdown_signal.signal();
+    let app = Router::new()
+        .route("/v0/ws", get(upgrade))
+        .with_state(Arc::new(bridge));
+
+    let listener = TcpListener::bind(addr).await?;
+    axum::serve(listener, app)
+        .with_graceful_shutdown(shutdown_signal)
+        .await?;
+
+    log::info!("WebSocket bridge stopped ...");
+
+    Ok(())
+}
+
+async fn upgrade(
+    State(bridge): State<Arc<WebSocketBridge>>,
+    headers: HeaderMap,
+    ws: WebSocketUpgrade,
+) -> Response {
+    // Browsers can't set custom headers for WebSocket connections, which is why the API key is
+    // alternatively accepted as part of the subscription.
+    let authenticated =
+        !bridge.auth.auth_config.enabled || bridge.auth.authenticate(&headers).is_some();
+    ws.on_upgrade(move |socket| async move {
+        if let Err(err) = handle(socket, bridge, authenticated).await {
+            log::debug!("WebSocket connection closed with error: {}", err);
+        }
+    })
+}
+
+async fn handle(
+    mut socket: WebSocket,
+    bridge: Arc<WebSocketBridge>,
+    authenticated: bool,
+) -> Result<(), Error> {
+    let subscription =
+        match tokio::time::timeout(SUBSCRIPTION_TIMEOUT, recv_subscription(&mut socket)).await {
+            Ok(Ok(Some(subscription))) => subscription,
+            Ok(Ok(None)) => return Ok(()),
+            Ok(Err(err)) => return Err(err),
+            Err(_) => {
+                let err = format!(
+                    "no subscription received within {}s",
+                    SUBSCRIPTION_TIMEOUT.as_secs()
+                );
+                send(&mut socket, &Push::Error(&err)).await?;
+                return Ok(());
+            }
+        };
+
+    if !authenticated {
+        let client = subscription.api_key.as_deref().and_then(|token| {
+            bridge
+                .auth
+                .auth_config
+                .tokens
+                .iter()
+                .find(|key| key.token == token)
+        });
+        match client {
+            Some(key) => log::debug!("Authenticated client: {}", key.client),
+            None => {
+                send(&mut socket, &Push::Error("Unauthenticated")).await?;
+                return Ok(());
+            }
+        }
+    }
+
+    let (tx, mut rx) = mpsc::channel::<String>(128);
+
+    if let Some(filter) = subscription.events {
+        let events = bridge.rpc.events().await;
+        let tx = tx.clone();
+        tokio::spawn(async move {
+            let mut events = std::pin::pin!(events);
+            loop {
+                let event = tokio::select! {
+                    _ = tx.closed() => break,
+                    event = events.next() => event,
+                };
+                let Some(event) = event else {
+                    break;
+                };
+                let Some(json) = filter_event(&filter, &event) else {
+                    continue;
+                };
+                if tx.send(json).await.is_err() {
+                    break;
+                }
+            }
+        });
+    }
+
+    if let Some(opts) = subscription.units {
+        let opts = StreamUnitsRequest {
+            poll_rate: opts.poll_rate,
+            max_backoff: opts.max_backoff,
+            category: opts.category,
+        };
+        let rpc = bridge.rpc.clone();
+        let tx = tx.clone();
+        tokio::spawn(async move {
+            let (subscriber, mut units_rx) =
+                rpc.subscribe(String::from("WebSocket units subscriber"));
+            tokio::spawn(crate::stream::stream_units(opts, rpc, subscriber));
+
+            while let Some(update) = units_rx.recv().await {
+                let json = match update {
+                    Ok(update) => serde_json::to_string(&Push::Units(&update)),
+                    Err(err) => serde_json::to_string(&Push::Error(err.message())),
+                };
+                match json {
+                    Ok(json) => {
+                        if tx.send(json).await.is_err() {
+                            break;
+                        }
+                    }
+                    Err(err) => log::error!("failed to serialize unit update: {}", err),
+                }
+            }
+        });
+    }
+
+    // drop the original sender so that the loop below ends once all streams ended
+    drop(tx);
+
+    let mut shutdown_signal = bridge.shutdown_signal.signal();
+    loop {
+        tokio::select! {
+            _ = &mut shutdown_signal => break,
+            msg = rx.recv() => {
+                let Some(msg) = msg else {
+                    break;
+                };
+                socket.send(Message::Text(msg.into())).await?;
+            }
+            incoming = socket.recv() => {
+                match incoming {
+                    Some(Ok(Message::Close(_))) | None => break,
+                    Some(Ok(_)) => {}
+                    Some(Err(err)) => return Err(err.into()),
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Wait for the [Subscription], which has to be the first message sent by the client. Returns
+/// `None` if the connection got closed or the client got told what is wrong with its message.
+async fn recv_subscription(socket: &mut WebSocket) -> Result<Option<Subscription>, Error> {
+    loop {
+        match socket.recv().await {
+            Some(Ok(Message::Text(text))) => {
+                return match serde_json::from_str::<Subscription>(text.as_str()) {
+                    Ok(subscription) => Ok(Some(subscription)),
+                    Err(err) => {
+                        let err = format!("invalid subscription: {err}");
+                        send(socket, &Push::Error(&err)).await?;
+                        Ok(None)
+                    }
+                };
+            }
+            Some(Ok(Message::Ping(_) | Message::Pong(_))) => continue,
+            Some(Ok(Message::Binary(_))) => {
+                send(socket, &Push::Error("expected a JSON text message")).await?;
+                return Ok(None);
+            }
+            Some(Ok(Message::Close(_))) | None => return Ok(None),
+            Some(Err(err)) => return Err(err.into()),
+        }
+    }
+}
+
+/// Serialize the event if it passes the filter.
+fn filter_event(filter: &EventsFilter, event: &StreamEventsResponse) -> Option<String> {
+    let push = serde_json::to_value(Push::Events(event))
+        .inspect_err(|err| log::error!("failed to serialize event: {}", err))
+        .ok()?;
+
+    if !filter.types.is_empty() {
+        let kind = push
+            .pointer("/message/event/type")
+            .and_then(|kind| kind.as_str())?;
+        if !filter.types.iter().any(|t| t == kind) {
+            return None;
+        }
+    }
+
+    Some(push.to_string())
+}
+
+async fn send(socket: &mut WebSocket, push: &Push<'_>) -> Result<(), Error> {
+    let json = serde_json::to_string(push)?;
+    socket.send(Message::Text(json.into())).await?;
+    Ok(())
+}
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error(transparent)]
+    WebSocket(#[from] axum::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use stubs::mission::v0::stream_events_response::{Event, MissionEndEvent, MissionStartEvent};
+
+    use super::*;
+
+    fn mission_start() -> StreamEventsResponse {
+        StreamEventsResponse {
+            time: 4.2,
+            event: Some(Event::MissionStart(MissionStartEvent {})),
+        }
+    }
+
+    fn mission_end() -> StreamEventsResponse {
+        StreamEventsResponse {
+            time: 8.4,
+            event: Some(Event::MissionEnd(MissionEndEvent {})),
+        }
+    }
+
+    #[test]
+    fn test_subscription() {
+        let subscription: Subscription = serde_json::from_str(
+            r#"{
+                "apiKey": "secret",
+                "events": { "types": ["missionStart"] },
+                "units": { "pollRate": 2, "category": 1 }
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(subscription.api_key.as_deref(), Some("secret"));
+        assert_eq!(subscription.events.unwrap().types, ["missionStart"]);
+        let units = subscription.units.unwrap();
+        assert_eq!(units.poll_rate, Some(2));
+        assert_eq!(units.max_backoff, None);
+        assert_eq!(units.category, 1);
+
+        let subscription: Subscription = serde_json::from_str(r#"{ "events": {} }"#).unwrap();
+        assert!(subscription.events.unwrap().types.is_empty());
+        assert!(subscription.units.is_none());
+
+        assert!(serde_json::from_str::<Subscription>(r#"{ "events": { "types": "x" } }"#).is_err());
+    }
+
+    #[test]
+    fn test_filter_event() {
+        let all = EventsFilter::default();
+        let json = filter_event(&all, &mission_start()).unwrap();
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&json).unwrap(),
+            serde_json::json!({
+                "stream": "events",
+                "message": { "time": 4.2, "event": { "type": "missionStart" } },
+            })
+        );
+        assert!(filter_event(&all, &mission_end()).is_some());
+
+        let start_only = EventsFilter {
+            types: vec!["missionStart".to_string()],
+        };
+        assert!(filter_event(&start_only, &mission_start()).is_some());
+        assert!(filter_event(&start_only, &mission_end()).is_none());
+    }
+}
@@ -0,0 +1,464 @@
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+use futures_util::TryFutureExt;
+use stubs::coalition::v0::GetGroupsRequest;
+use stubs::coalition::v0::coalition_service_server::CoalitionService;
+use stubs::common::v0::{Coalition, GroupCategory, Position, Unit};
+use stubs::group::v0::GetUnitsRequest;
+use stubs::group::v0::group_service_server::GroupService;
+use stubs::mission::v0::StreamEventsResponse;
+use stubs::mission::v0::stream_events_response::{Event, ZoneEnterEvent, ZoneLeaveEvent};
+use stubs::zone::v0::zone::Shape;
+use stubs::zone::v0::zone_service_server::ZoneService;
+use stubs::zone::v0::{GetZonesRequest, Zone};
+use tokio::time::MissedTickBehavior;
+use tonic::{Code, Request, Status};
+
+use crate::config::{ZoneMonitorConfig, ZonesConfig};
+use crate::fps::event_time;
+use crate::rpc::MissionRpc;
+use crate::shutdown::ShutdownHandle;
+
+/// Polls the positions of all active units and emits a `ZoneEnterEvent`/`ZoneLeaveEvent` whenever
+/// a unit matching the filters of a monitor entered or left its trigger zone. Units already inside
+/// a zone when the monitoring starts are reported as entering it.
+pub async fn run_in_background(
+    rpc: MissionRpc,
+    config: ZonesConfig,
+    shutdown_signal: ShutdownHandle,
+) {
+    let mut interval = tokio::time::interval(Duration::from_secs(config.poll_rate.max(1)));
+    interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    let mut shutdown_signal = shutdown_signal.signal();
+
+    // the zones are resolved lazily, as the mission might not be ready yet
+    let mut monitors: Option<Vec<Monitor>> = None;
+
+    loop {
+        // wait for either the shutdown signal or the next tick, whatever happens first
+        tokio::select! {
+            _ = &mut shutdown_signal => break,
+            _ = interval.tick() => {},
+        }
+
+        if monitors.is_none() {
+            match resolve(&rpc, &config.monitors).await {
+                Ok(resolved) => monitors = Some(resolved),
+                Err(err) => {
+                    log::debug!("failed to get trigger zones for the zone monitor: {}", err);
+                    continue;
+                }
+            }
+        }
+
+        let Some(monitors) = monitors.as_mut() else {
+            continue;
+        };
+        if monitors.is_empty() {
+            // none of the zones exist, nothing to monitor
+            break;
+        }
+
+        let units = match get_units(&rpc).await {
+            Ok(units) => units,
+            Err(err) => {
+                log::error!("failed to get units for the zone monitor: {}", err);
+                continue;
+            }
+        };
+
+        for monitor in monitors.iter_mut() {
+            for event in monitor.update(&units) {
+                rpc.event(StreamEventsResponse {
+                    time: event_time(),
+                    event: Some(event),
+                })
+                .await;
+            }
+        }
+    }
+}
+
+/// A trigger zone and the units currently inside of it.
+struct Monitor {
+    config: ZoneMonitorConfig,
+    zone: Zone,
+    inside: HashSet<String>,
+}
+
+impl Monitor {
+    /// Update the units inside of the zone and return the events for all units that entered or
+    /// left it since the last update.
+    fn update(&mut self, units: &HashMap<String, Unit>) -> Vec<Event> {
+        let mut events = Vec::new();
+        let mut inside = HashSet::new();
+
+        for unit in units.values() {
+            if !self.matches(unit) || !unit.position.as_ref().is_some_and(|p| self.contains(p)) {
+                continue;
+            }
+
+            inside.insert(unit.name.clone());
+            if !self.inside.contains(&unit.name) {
+                events.push(Event::ZoneEnter(ZoneEnterEvent {
+                    zone: self.zone.name.clone(),
+                    unit: Some(unit.clone()),
+                }));
+            }
+        }
+
+        for name in self.inside.difference(&inside) {
+            // units that are gone (e.g. died inside of the zone) did not leave it
+            if let Some(unit) = units.get(name) {
+                events.push(Event::ZoneLeave(ZoneLeaveEvent {
+                    zone: self.zone.name.clone(),
+                    unit: Some(unit.clone()),
+                }));
+            }
+        }
+
+        self.inside = inside;
+        events
+    }
+
+    /// Whether the unit matches the filters of the monitor.
+    fn matches(&self, unit: &Unit) -> bool {
+        let config = &self.config;
+        if config
+            .coalition
+            .is_some_and(|coalition| coalition.to_proto() != unit.coalition)
+        {
+            return false;
+        }
+
+        if !config.categories.is_empty() {
+            let category = unit
+                .group
+                .as_ref()
+                .and_then(|group| GroupCategory::try_from(group.category).ok())
+                .unwrap_or(GroupCategory::Unspecified);
+            if !config.categories.iter().any(|c| c.to_proto() == category) {
+                return false;
+            }
+        }
+
+        if !config.unit_types.is_empty() && !config.unit_types.contains(&unit.r#type) {
+            return false;
+        }
+
+        !config.players_only || unit.player_name.is_some()
+    }
+
+    /// Whether the position is inside of the zone (ignoring the altitude).
+    fn contains(&self, position: &Position) -> bool {
+        match &self.zone.shape {
+            Some(Shape::Circle(circle)) => circle.center.as_ref().is_some_and(|center| {
+                (position.u - center.u).hypot(position.v - center.v) <= circle.radius
+            }),
+            Some(Shape::Quad(quad)) => polygon_contains(&quad.points, position),
+            None => false,
+        }
+    }
+}
+
+/// Find the zones of all monitors. Monitors of zones that don't exist are skipped.
+async fn resolve(rpc: &MissionRpc, configs: &[ZoneMonitorConfig]) -> Result<Vec<Monitor>, Status> {
+    let zones = rpc
+        .get_zones(Request::new(GetZonesRequest {}))
+        .await?
+        .into_inner()
+        .zones;
+
+    let mut monitors = Vec::with_capacity(configs.len());
+    for config in configs {
+        match zones.iter().find(|zone| zone.name == config.zone) {
+            Some(zone) => monitors.push(Monitor {
+                config: config.clone(),
+                zone: zone.clone(),
+                inside: HashSet::new(),
+            }),
+            None => log::warn!("Trigger zone `{}` to monitor does not exist", config.zone),
+        }
+    }
+
+    Ok(monitors)
+}
+
+/// Get all active units of the mission by their name.
+async fn get_units(rpc: &MissionRpc) -> Result<HashMap<String, Unit>, Status> {
+    let groups = futures_util::future::try_join_all(
+        [Coalition::Blue, Coalition::Red, Coalition::Neutral].map(|coalition| {
+            rpc.get_groups(Request::new(GetGroupsRequest {
+                coalition: coalition.into(),
+                category: GroupCategory::Unspecified.into(),
+            }))
+            .map_ok(|res| res.into_inner().groups)
+        }),
+    )
+    .await?
+    .into_iter()
+    .flatten();
+
+    let units = futures_util::future::try_join_all(groups.map(|group| {
+        rpc.get_units(Request::new(GetUnitsRequest {
+            group_name: group.name,
+            active: Some(true),
+        }))
+        .map_ok(|res| res.into_inner().units)
+        .or_else(|err| async move {
+            // the group got destroyed in the meantime
+            if err.code() == Code::NotFound {
+                Ok(Vec::new())
+            } else {
+                Err(err)
+            }
+        })
+    }))
+    .await?;
+
+    Ok(units
+        .into_iter()
+        .flatten()
+        .map(|unit| (unit.name.clone(), unit))
+        .collect())
+}
+
+/// Whether the position is inside of the polygon, using the ray casting algorithm on the u/v
+/// coordinates.
+fn polygon_contains(points: &[Position], position: &Position) -> bool {
+    let mut inside = false;
+    let mut j = points.len().wrapping_sub(1);
+    for (i, a) in points.iter().enumerate() {
+        let b = &points[j];
+        if (a.v > position.v) != (b.v > position.v)
+            && position.u < (b.u - a.u) * (position.v - a.v) / (b.v - a.v) + a.u
+        {
+            inside = !inside;
+        }
+        j = i;
+    }
+    inside
+}
+
+#[cfg(test)]
+mod tests {
+    use stubs::common::v0::Group;
+    use stubs::zone::v0::zone::{Circle, Quad};
+
+    use super::*;
+    use crate::config::{CoalitionFilter, ZoneUnitCategory};
+
+    fn position(u: f64, v: f64) -> Position {
+        Position {
+            u,
+            v,
+            ..Default::default()
+        }
+    }
+
+    fn config() -> ZoneMonitorConfig {
+        ZoneMonitorConfig {
+            zone: "Target".to_string(),
+            coalition: None,
+            categories: Vec::new(),
+            unit_types: Vec::new(),
+            players_only: false,
+        }
+    }
+
+    fn circle(config: ZoneMonitorConfig) -> Monitor {
+        Monitor {
+            config,
+            zone: Zone {
+                name: "Target".to_string(),
+                shape: Some(Shape::Circle(Circle {
+                    center: Some(position(100.0, 100.0)),
+                    radius: 50.0,
+                })),
+                ..Default::default()
+            },
+            inside: HashSet::new(),
+        }
+    }
+
+    /// A quad that is not a rectangle, with a concave corner at (50, 50).
+    fn quad() -> Monitor {
+        Monitor {
+            config: config(),
+            zone: Zone {
+                name: "Target".to_string(),
+                shape: Some(Shape::Quad(Quad {
+                    points: vec![
+                        position(0.0, 0.0),
+                        position(100.0, 0.0),
+                        position(50.0, 50.0),
+                        position(0.0, 100.0),
+                    ],
+                })),
+                ..Default::default()
+            },
+            inside: HashSet::new(),
+        }
+    }
+
+    fn unit(name: &str, u: f64, v: f64) -> Unit {
+        let mut unit = Unit {
+            name: name.to_string(),
+            r#type: "F-16C_50".to_string(),
+            position: Some(position(u, v)),
+            group: Some(Group::default()),
+            ..Default::default()
+        };
+        unit.set_coalition(Coalition::Blue);
+        unit.group
+            .as_mut()
+            .unwrap()
+            .set_category(GroupCategory::Airplane);
+        unit
+    }
+
+    fn units(units: impl IntoIterator<Item = Unit>) -> HashMap<String, Unit> {
+        units
+            .into_iter()
+            .map(|unit| (unit.name.clone(), unit))
+            .collect()
+    }
+
+    /// The events as sorted `(entered, unit name)` pairs.
+    fn summary(events: &[Event]) -> Vec<(bool, String)> {
+        let mut summary = events
+            .iter()
+            .map(|event| match event {
+                Event::ZoneEnter(event) => (true, event.unit.as_ref().unwrap().name.clone()),
+                Event::ZoneLeave(event) => (false, event.unit.as_ref().unwrap().name.clone()),
+                _ => unreachable!(),
+            })
+            .collect::<Vec<_>>();
+        summary.sort();
+        summary
+    }
+
+    #[test]
+    fn test_circle_contains() {
+        let monitor = circle(config());
+        assert!(monitor.contains(&position(100.0, 100.0)));
+        assert!(monitor.contains(&position(150.0, 100.0)));
+        assert!(monitor.contains(&position(130.0, 130.0)));
+        assert!(!monitor.contains(&position(140.0, 140.0)));
+        assert!(!monitor.contains(&position(0.0, 0.0)));
+    }
+
+    #[test]
+    fn test_quad_contains() {
+        let monitor = quad();
+        assert!(monitor.contains(&position(10.0, 10.0)));
+        assert!(monitor.contains(&position(40.0, 40.0)));
+        assert!(monitor.contains(&position(80.0, 5.0)));
+        assert!(monitor.contains(&position(5.0, 80.0)));
+        // in the concave corner
+        assert!(!monitor.contains(&position(60.0, 60.0)));
+        assert!(!monitor.contains(&position(-1.0, 10.0)));
+        assert!(!monitor.contains(&position(10.0, 101.0)));
+    }
+
+    #[test]
+    fn test_polygon_contains_degenerate() {
+        assert!(!polygon_contains(&[], &position(0.0, 0.0)));
+        assert!(!polygon_contains(
+            &[position(0.0, 0.0), position(10.0, 10.0)],
+            &position(5.0, 5.0)
+        ));
+    }
+
+    #[test]
+    fn test_enter_and_leave() {
+        let mut monitor = circle(config());
+
+        // units already inside are reported as entering the zone
+        let events = monitor.update(&units([unit("a", 100.0, 100.0), unit("b", 0.0, 0.0)]));
+        assert_eq!(summary(&events), vec![(true, "a".to_string())]);
+
+        // no events while nothing changes
+        let events = monitor.update(&units([unit("a", 110.0, 100.0), unit("b", 0.0, 0.0)]));
+        assert!(events.is_empty());
+
+        let events = monitor.update(&units([unit("a", 0.0, 0.0), unit("b", 90.0, 90.0)]));
+        assert_eq!(
+            summary(&events),
+            vec![(false, "a".to_string()), (true, "b".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_died_inside() {
+        let mut monitor = circle(config());
+        monitor.update(&units([unit("a", 100.0, 100.0)]));
+
+        // units that are gone did not leave the zone
+        let events = monitor.update(&units([]));
+        assert!(events.is_empty());
+        assert!(monitor.inside.is_empty());
+
+        // and enter it again if they respawn inside of it
+        let events = monitor.update(&units([unit("a", 100.0, 100.0)]));
+        assert_eq!(summary(&events), vec![(true, "a".to_string())]);
+    }
+
+    #[test]
+    fn test_filter_coalition() {
+        let monitor = circle(ZoneMonitorConfig {
+            coalition: Some(CoalitionFilter::Red),
+            ..config()
+        });
+        let mut red = unit("a", 100.0, 100.0);
+        red.set_coalition(Coalition::Red);
+        assert!(monitor.matches(&red));
+        assert!(!monitor.matches(&unit("b", 100.0, 100.0)));
+    }
+
+    #[test]
+    fn test_filter_categories() {
+        let monitor = circle(ZoneMonitorConfig {
+            categories: vec![ZoneUnitCategory::Helicopter, ZoneUnitCategory::Ground],
+            ..config()
+        });
+        let mut helicopter = unit("a", 100.0, 100.0);
+        helicopter
+            .group
+            .as_mut()
+            .unwrap()
+            .set_category(GroupCategory::Helicopter);
+        assert!(monitor.matches(&helicopter));
+        assert!(!monitor.matches(&unit("b", 100.0, 100.0)));
+
+        let mut without_group = unit("c", 100.0, 100.0);
+        without_group.group = None;
+        assert!(!monitor.matches(&without_group));
+    }
+
+    #[test]
+    fn test_filter_unit_types() {
+        let monitor = circle(ZoneMonitorConfig {
+            unit_types: vec!["FA-18C_hornet".to_string()],
+            ..config()
+        });
+        let mut hornet = unit("a", 100.0, 100.0);
+        hornet.r#type = "FA-18C_hornet".to_string();
+        assert!(monitor.matches(&hornet));
+        assert!(!monitor.matches(&unit("b", 100.0, 100.0)));
+    }
+
+    #[test]
+    fn test_filter_players_only() {
+        let mut monitor = circle(ZoneMonitorConfig {
+            players_only: true,
+            ..config()
+        });
+        let mut player = unit("a", 100.0, 100.0);
+        player.player_name = Some("Pilot".to_string());
+
+        let events = monitor.update(&units([player, unit("b", 100.0, 100.0)]));
+        assert_eq!(summary(&events), vec![(true, "a".to_string())]);
+    }
+}
@@ -6,6 +6,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     println!("cargo:rerun-if-changed=../protos/dcs");
 
+    let out_dir = std::path::PathBuf::from(std::env::var("OUT_DIR")?);
+
     tonic_build::configure()
         .type_attribute(".", "#[derive(::serde::Serialize, ::serde::Deserialize)]")
         .type_attribute(".", "#[serde(rename_all = \"camelCase\")]")
@@ -65,6 +67,51 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             "dcs.mission.v0.StreamEventsResponse.GroupCommandEvent.details",
             r#"#[serde(with = "crate::utils::proto_struct")]"#,
         )
+        .field_attribute(
+            "dcs.mission.v0.AddMissionOtherCommandRequest.details",
+            r#"#[serde(with = "crate::utils::proto_struct")]"#,
+        )
+        .field_attribute(
+            "dcs.mission.v0.AddCoalitionOtherCommandRequest.details",
+            r#"#[serde(with = "crate::utils::proto_struct")]"#,
+        )
+        .field_attribute(
+            "dcs.mission.v0.AddGroupOtherCommandRequest.details",
+            r#"#[serde(with = "crate::utils::proto_struct")]"#,
+        )
+        .field_attribute(
+            "dcs.mission.v0.CommandItem.details",
+            r#"#[serde(default, with = "crate::utils::proto_struct")]"#,
+        )
+        .field_attribute(
+            "dcs.mission.v0.OtherCommand.details",
+            r#"#[serde(default, with = "crate::utils::proto_struct")]"#,
+        )
+        .field_attribute(
+            "dcs.mission.v0.StreamEventsResponse.CustomEvent.payload",
+            r#"#[serde(with = "crate::utils::proto_struct")]"#,
+        )
+        .field_attribute(
+            "dcs.chat.v0.ListenResponse.arguments",
+            r#"#[serde(with = "crate::utils::proto_struct")]"#,
+        )
+        .field_attribute(
+            "dcs.custom.v0.CallRequest.params",
+            r#"#[serde(with = "crate::utils::proto_struct")]"#,
+        )
+        .field_attribute(
+            "dcs.custom.v0.CallResponse.result",
+            r#"#[serde(with = "crate::utils::proto_struct")]"#,
+        )
+        .field_attribute(
+            "dcs.hook.v0.GetCurrentMissionResponse.mission",
+            r#"#[serde(with = "crate::utils::proto_struct")]"#,
+        )
+        .field_attribute(
+            "dcs.hook.v0.GetMissionOptionsResponse.options",
+            r#"#[serde(with = "crate::utils::proto_struct")]"#,
+        )
+        .file_descriptor_set_path(out_dir.join("dcs_descriptor.bin"))
         .build_server(cfg!(feature = "server"))
         .build_client(cfg!(feature = "client"))
         .compile_protos(&["../protos/dcs/dcs.proto"], &["../protos"])?;
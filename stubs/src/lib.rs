@@ -4,29 +4,42 @@
 #![allow(clippy::large_enum_variant)]
 
 pub mod atmosphere;
+pub mod ban;
+pub mod chat;
 pub mod coalition;
 pub mod common;
 pub mod controller;
 pub mod custom;
+pub mod gatekeeper;
 pub mod group;
 pub mod hook;
+pub mod land;
 pub mod metadata;
 pub mod mission;
 pub mod net;
+pub mod player;
 pub mod srs;
 pub mod timer;
 pub mod trigger;
 pub mod unit;
 mod utils;
 pub mod world;
+pub mod zone;
+
+/// Encoded file descriptor set of all DCS-gRPC protos (used for gRPC server reflection).
+pub const FILE_DESCRIPTOR_SET: &[u8] = tonic::include_file_descriptor_set!("dcs_descriptor");
 
 #[cfg(test)]
 mod tests {
     use super::common::v0::{
         Airbase, AirbaseCategory, Coalition, Initiator, Position, Unit, initiator,
     };
-    use super::mission::v0::{StreamEventsResponse, stream_events_response as event};
+    use super::mission::v0::{
+        CommandItem, CommandMenu, ListCommandsResponse, OtherCommand, StreamEventsResponse,
+        stream_events_response as event,
+    };
     use super::world::v0::GetAirbasesResponse;
+    use super::zone::v0::{GetZonesResponse, Zone, zone};
     use crate::common::v0::{Orientation, Velocity};
 
     #[test]
@@ -42,6 +55,33 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_custom_event_deserialization() {
+        let event: StreamEventsResponse = serde_json::from_str(
+            r#"{"time":4.2,"event":{"type":"custom","name":"cargoDelivered","payload":{"weight":500}}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            event,
+            StreamEventsResponse {
+                time: 4.2,
+                event: Some(event::Event::Custom(event::CustomEvent {
+                    name: "cargoDelivered".to_string(),
+                    payload: Some(prost_types::Struct {
+                        fields: [(
+                            "weight".to_string(),
+                            prost_types::Value {
+                                kind: Some(prost_types::value::Kind::NumberValue(500.0)),
+                            },
+                        )]
+                        .into_iter()
+                        .collect(),
+                    }),
+                })),
+            }
+        );
+    }
+
     // Note that this string simulates the response from Lua. This is important as it is
     // _after_ increment changes to enums to cater to gRPC enum indexing where 0 is not allowed
     // for responses.
@@ -180,4 +220,139 @@ mod tests {
             }
         );
     }
+
+    // Submenus (and commands added without details) are returned without any details.
+    #[test]
+    fn test_command_menu_deserialization() {
+        let resp: ListCommandsResponse = serde_json::from_str(
+            r#"
+                {
+                    "mission": {
+                        "items": [
+                            {
+                                "name": "Tanker",
+                                "path": ["Tanker"],
+                                "subMenu": true,
+                                "children": [
+                                    {
+                                        "name": "Request",
+                                        "path": ["Tanker", "Request"],
+                                        "subMenu": false,
+                                        "details": {"callsign": "Texaco"},
+                                        "children": []
+                                    }
+                                ]
+                            }
+                        ],
+                        "otherCommands": [{"name": "Smoke"}]
+                    },
+                    "coalitions": [],
+                    "groups": []
+                }
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            resp,
+            ListCommandsResponse {
+                mission: Some(CommandMenu {
+                    items: vec![CommandItem {
+                        name: "Tanker".to_string(),
+                        path: vec!["Tanker".to_string()],
+                        sub_menu: true,
+                        details: None,
+                        children: vec![CommandItem {
+                            name: "Request".to_string(),
+                            path: vec!["Tanker".to_string(), "Request".to_string()],
+                            sub_menu: false,
+                            details: Some(prost_types::Struct {
+                                fields: [(
+                                    "callsign".to_string(),
+                                    prost_types::Value {
+                                        kind: Some(prost_types::value::Kind::StringValue(
+                                            "Texaco".to_string()
+                                        )),
+                                    },
+                                )]
+                                .into_iter()
+                                .collect(),
+                            }),
+                            children: vec![],
+                        }],
+                    }],
+                    other_commands: vec![OtherCommand {
+                        name: "Smoke".to_string(),
+                        details: None,
+                    }],
+                }),
+                coalitions: vec![],
+                groups: vec![],
+            }
+        );
+    }
+
+    // Simulates the zones as exported by `lua/DCS-gRPC/methods/zone.lua`, with the shape nested
+    // under the name of its oneof.
+    #[test]
+    fn test_zone_deserialization() {
+        let position = r#"{"lat": 1, "lon": 2, "alt": 3, "u": 4, "v": 5}"#;
+        let resp: GetZonesResponse = serde_json::from_str(&format!(
+            r#"
+                {{
+                    "zones": [
+                        {{
+                            "id": 1,
+                            "name": "Circle",
+                            "shape": {{
+                                "circle": {{ "center": {position}, "radius": 500 }}
+                            }},
+                            "properties": [{{ "key": "spawn", "value": "true" }}]
+                        }},
+                        {{
+                            "id": 2,
+                            "name": "Quad",
+                            "shape": {{
+                                "quad": {{ "points": [{position}, {position}] }}
+                            }},
+                            "properties": []
+                        }}
+                    ]
+                }}
+            "#
+        ))
+        .unwrap();
+
+        let position = Position {
+            lat: 1.0,
+            lon: 2.0,
+            alt: 3.0,
+            u: 4.0,
+            v: 5.0,
+        };
+        assert_eq!(
+            resp.zones,
+            vec![
+                Zone {
+                    id: 1,
+                    name: "Circle".to_string(),
+                    shape: Some(zone::Shape::Circle(zone::Circle {
+                        center: Some(position),
+                        radius: 500.0,
+                    })),
+                    properties: vec![zone::Property {
+                        key: "spawn".to_string(),
+                        value: "true".to_string(),
+                    }],
+                },
+                Zone {
+                    id: 2,
+                    name: "Quad".to_string(),
+                    shape: Some(zone::Shape::Quad(zone::Quad {
+                        points: vec![position, position],
+                    })),
+                    properties: vec![],
+                },
+            ]
+        );
+    }
 }
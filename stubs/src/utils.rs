@@ -111,13 +111,83 @@ pub mod proto_struct {
             M: MapAccess<'de>,
         {
             let mut fields = BTreeMap::new();
-            while let Some((key, value)) = access.next_entry::<String, ValueDe>()? {
-                fields.insert(key, value.0);
+            while let Some((key, value)) = access.next_entry::<KeyDe, ValueDe>()? {
+                fields.insert(key.0, value.0);
             }
             Ok(StructDe(Struct { fields }))
         }
     }
 
+    /// Deserializable key of a [prost_types::Struct]. Lua tables can also have number or boolean
+    /// keys (e.g. sparse arrays), which are converted to strings.
+    struct KeyDe(String);
+
+    impl<'de> Deserialize<'de> for KeyDe {
+        fn deserialize<D>(de: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            de.deserialize_any(KeyVisitor)
+        }
+    }
+
+    struct KeyVisitor;
+
+    impl<'de> Visitor<'de> for KeyVisitor {
+        type Value = KeyDe;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a string, number or boolean key")
+        }
+
+        fn visit_bool<E>(self, v: bool) -> Result<Self::Value, E>
+        where
+            E: serde::de::Error,
+        {
+            Ok(KeyDe(v.to_string()))
+        }
+
+        fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+        where
+            E: serde::de::Error,
+        {
+            Ok(KeyDe(v.to_string()))
+        }
+
+        fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+        where
+            E: serde::de::Error,
+        {
+            Ok(KeyDe(v.to_string()))
+        }
+
+        fn visit_f64<E>(self, v: f64) -> Result<Self::Value, E>
+        where
+            E: serde::de::Error,
+        {
+            // whole numbers are used as `1` instead of `1.0`, the same as integers
+            if v.fract() == 0.0 && v.abs() < i64::MAX as f64 {
+                Ok(KeyDe((v as i64).to_string()))
+            } else {
+                Ok(KeyDe(v.to_string()))
+            }
+        }
+
+        fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+        where
+            E: serde::de::Error,
+        {
+            Ok(KeyDe(v.to_string()))
+        }
+
+        fn visit_string<E>(self, v: String) -> Result<Self::Value, E>
+        where
+            E: serde::de::Error,
+        {
+            Ok(KeyDe(v))
+        }
+    }
+
     struct ValueVisitor;
 
     impl ValueVisitor {
@@ -178,22 +248,16 @@ pub mod proto_struct {
         where
             E: serde::de::Error,
         {
-            i32::try_from(v)
-                .map_err(|_| {
-                    serde::de::Error::invalid_type(serde::de::Unexpected::Other("i64"), &self)
-                })
-                .and_then(|v| self.visit_number(v))
+            // all numbers of a google.protobuf.Value are doubles anyway
+            self.visit_number(v as f64)
         }
 
         fn visit_i128<E>(self, v: i128) -> Result<Self::Value, E>
         where
             E: serde::de::Error,
         {
-            i32::try_from(v)
-                .map_err(|_| {
-                    serde::de::Error::invalid_type(serde::de::Unexpected::Other("i64"), &self)
-                })
-                .and_then(|v| self.visit_number(v))
+            // all numbers of a google.protobuf.Value are doubles anyway
+            self.visit_number(v as f64)
         }
 
         fn visit_u8<E>(self, v: u8) -> Result<Self::Value, E>
@@ -221,22 +285,16 @@ pub mod proto_struct {
         where
             E: serde::de::Error,
         {
-            u32::try_from(v)
-                .map_err(|_| {
-                    serde::de::Error::invalid_type(serde::de::Unexpected::Other("i64"), &self)
-                })
-                .and_then(|v| self.visit_number(v))
+            // all numbers of a google.protobuf.Value are doubles anyway
+            self.visit_number(v as f64)
         }
 
         fn visit_u128<E>(self, v: u128) -> Result<Self::Value, E>
         where
             E: serde::de::Error,
         {
-            u32::try_from(v)
-                .map_err(|_| {
-                    serde::de::Error::invalid_type(serde::de::Unexpected::Other("i64"), &self)
-                })
-                .and_then(|v| self.visit_number(v))
+            // all numbers of a google.protobuf.Value are doubles anyway
+            self.visit_number(v as f64)
         }
 
         fn visit_f32<E>(self, v: f32) -> Result<Self::Value, E>
@@ -308,6 +366,8 @@ pub mod proto_struct {
     mod tests {
         use prost_types::value::Kind;
         use prost_types::{ListValue, Struct, Value};
+        use serde::Deserialize;
+        use serde::de::value::MapDeserializer;
 
         #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
         struct Message {
@@ -388,6 +448,60 @@ pub mod proto_struct {
             assert_eq!(serde_json::from_str::<Message>(&json).unwrap(), m);
         }
 
+        #[test]
+        fn test_large_integers() {
+            let json = r#"{"details":{"i64":-4294967296,"u64":18446744073709551615}}"#;
+            let m = serde_json::from_str::<Message>(json).unwrap();
+            let fields = m.details.unwrap().fields;
+            assert_eq!(fields["i64"].kind, Some(Kind::NumberValue(-4294967296.0)));
+            assert_eq!(
+                fields["u64"].kind,
+                Some(Kind::NumberValue(18446744073709551615.0))
+            );
+        }
+
+        #[test]
+        fn test_integer_keys() {
+            // e.g. a sparse Lua array
+            let de = MapDeserializer::<_, serde::de::value::Error>::new(
+                [(1i64, "first"), (2, "second"), (5, "fifth")].into_iter(),
+            );
+            let fields = super::StructDe::deserialize(de).unwrap().0.fields;
+            assert_eq!(
+                fields.keys().map(String::as_str).collect::<Vec<_>>(),
+                vec!["1", "2", "5"]
+            );
+            assert_eq!(
+                fields["5"].kind,
+                Some(Kind::StringValue("fifth".to_string()))
+            );
+        }
+
+        #[test]
+        fn test_number_and_bool_keys() {
+            let de = MapDeserializer::<_, serde::de::value::Error>::new(
+                [(1.0f64, 10u64), (2.5, 20), (-3.0, 1 << 40)].into_iter(),
+            );
+            let fields = super::StructDe::deserialize(de).unwrap().0.fields;
+            assert_eq!(
+                fields.keys().map(String::as_str).collect::<Vec<_>>(),
+                vec!["-3", "1", "2.5"]
+            );
+            assert_eq!(
+                fields["-3"].kind,
+                Some(Kind::NumberValue((1u64 << 40) as f64))
+            );
+
+            let de = MapDeserializer::<_, serde::de::value::Error>::new(
+                [(true, "yes"), (false, "no")].into_iter(),
+            );
+            let fields = super::StructDe::deserialize(de).unwrap().0.fields;
+            assert_eq!(
+                fields["true"].kind,
+                Some(Kind::StringValue("yes".to_string()))
+            );
+        }
+
         #[test]
         fn test_list() {
             let m = create_message(